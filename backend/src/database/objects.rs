@@ -137,7 +137,7 @@ pub fn from_canvas_model(model: CanvasModel) -> Result<(StateInfo, ConfigJson)>
 			updated_at: model.updated_at,
 			is_edited: false
 		},
-		serde_json::from_str(&model.json)?
+		serde_json::from_str::<ConfigJson>(&model.json)?.migrate()
 	))
 }
 
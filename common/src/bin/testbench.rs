@@ -0,0 +1,39 @@
+use std::path::Path;
+use std::process::exit;
+
+use circuit_sim_common::testbench;
+
+
+fn main() {
+	let paths: Vec<String> = std::env::args().skip(1).collect();
+
+	if paths.is_empty() {
+		eprintln!("Usage: testbench <testbench.json>...");
+		exit(2);
+	}
+
+	let mut failed = 0;
+
+	for path in &paths {
+		match testbench::run_file(Path::new(path)) {
+			Ok(report) => {
+				println!("{report}");
+
+				if !report.is_success() {
+					failed += 1;
+				}
+			}
+
+			Err(e) => {
+				eprintln!("{path}: {e}");
+				failed += 1;
+			}
+		}
+	}
+
+	println!("\n{} passed, {failed} failed", paths.len() - failed);
+
+	if failed != 0 {
+		exit(1);
+	}
+}
@@ -0,0 +1,185 @@
+use crate::expr::{Assignment, Expr};
+use crate::object::ObjectType;
+
+
+/// Where a gate input or a circuit output reads its value from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+	/// Index into `Circuit::inputs`
+	Input(usize),
+	/// Index into `Circuit::gates`
+	Gate(usize)
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Gate {
+	pub type_of: ObjectType,
+	pub inputs: Vec<Signal>
+}
+
+
+/// A gate network. Inputs become Switches, outputs become LEDs.
+#[derive(Debug, Clone, Default)]
+pub struct Circuit {
+	pub inputs: Vec<String>,
+	pub outputs: Vec<(String, Signal)>,
+	pub gates: Vec<Gate>
+}
+
+impl Circuit {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Synthesizes the assignments into 2-input gates. Identical sub-expressions share gates.
+	pub fn from_assignments(assignments: &[Assignment]) -> Result<Self, String> {
+		let mut this = Self::new();
+
+		// Inputs are in order of first appearance.
+		let mut inputs = Vec::new();
+		assignments.iter().for_each(|a| a.expr.variables(&mut inputs));
+
+		for name in inputs {
+			if assignments.iter().any(|a| a.output == name) {
+				return Err(format!("`{}` is used as both an input and an output", name));
+			}
+
+			this.add_input(&name);
+		}
+
+		for assignment in assignments {
+			match assignment.expr.clone().simplify() {
+				Expr::Const(v) => return Err(format!("Output `{}` is always {}. Constant outputs aren't supported", assignment.output, v as u8)),
+
+				expr => {
+					let signal = this.add_expr(&expr);
+					this.add_output(&assignment.output, signal);
+				}
+			}
+		}
+
+		Ok(this)
+	}
+
+	/// Returns the existing input if one already has the name.
+	pub fn add_input(&mut self, name: &str) -> Signal {
+		if let Some(index) = self.inputs.iter().position(|v| v == name) {
+			Signal::Input(index)
+		} else {
+			self.inputs.push(name.to_string());
+			Signal::Input(self.inputs.len() - 1)
+		}
+	}
+
+	/// Returns the existing gate if an identical one was already added.
+	pub fn add_gate(&mut self, type_of: ObjectType, inputs: Vec<Signal>) -> Signal {
+		let gate = Gate { type_of, inputs };
+
+		if let Some(index) = self.gates.iter().position(|g| g == &gate) {
+			Signal::Gate(index)
+		} else {
+			self.gates.push(gate);
+			Signal::Gate(self.gates.len() - 1)
+		}
+	}
+
+	pub fn add_output(&mut self, name: &str, signal: Signal) {
+		self.outputs.push((name.to_string(), signal));
+	}
+
+	fn add_expr(&mut self, expr: &Expr) -> Signal {
+		match expr {
+			Expr::Var(name) => self.add_input(name),
+
+			Expr::Not(v) => {
+				let v = self.add_expr(v);
+				self.add_gate(ObjectType::NotGate, vec![v])
+			}
+
+			Expr::And(l, r) => self.add_binary(ObjectType::AndGate, l, r),
+			Expr::Or(l, r) => self.add_binary(ObjectType::OrGate, l, r),
+			Expr::Xor(l, r) => self.add_binary(ObjectType::XorGate, l, r),
+
+			Expr::Const(_) => unreachable!("constants are removed by simplify")
+		}
+	}
+
+	fn add_binary(&mut self, type_of: ObjectType, left: &Expr, right: &Expr) -> Signal {
+		let left = self.add_expr(left);
+		let right = self.add_expr(right);
+
+		self.add_gate(type_of, vec![left, right])
+	}
+
+	/// Logic level of each gate. Inputs are level 0.
+	pub fn gate_levels(&self) -> Vec<usize> {
+		let mut levels: Vec<Option<usize>> = vec![None; self.gates.len()];
+
+		for index in 0..self.gates.len() {
+			self.gate_level(index, &mut levels, &mut Vec::new());
+		}
+
+		levels.into_iter().map(Option::unwrap_or_default).collect()
+	}
+
+	fn gate_level(&self, index: usize, levels: &mut Vec<Option<usize>>, visiting: &mut Vec<usize>) -> usize {
+		if let Some(level) = levels[index] {
+			return level;
+		}
+
+		// Feedback loop. Break it here.
+		if visiting.contains(&index) {
+			return 0;
+		}
+
+		visiting.push(index);
+
+		let level = 1 + self.gates[index].inputs.iter()
+			.map(|s| match *s {
+				Signal::Input(_) => 0,
+				Signal::Gate(i) => self.gate_level(i, levels, visiting)
+			})
+			.max()
+			.unwrap_or_default();
+
+		visiting.pop();
+
+		levels[index] = Some(level);
+
+		level
+	}
+
+	/// Evaluates the outputs for the input values. Feedback loops are read as `false`.
+	pub fn evaluate(&self, inputs: &[bool]) -> Vec<bool> {
+		let mut values: Vec<Option<bool>> = vec![None; self.gates.len()];
+
+		self.outputs.iter()
+			.map(|(_, signal)| self.signal_value(*signal, inputs, &mut values))
+			.collect()
+	}
+
+	fn signal_value(&self, signal: Signal, inputs: &[bool], values: &mut Vec<Option<bool>>) -> bool {
+		match signal {
+			Signal::Input(i) => inputs.get(i).copied().unwrap_or_default(),
+
+			Signal::Gate(i) => {
+				if let Some(v) = values[i] {
+					return v;
+				}
+
+				values[i] = Some(false);
+
+				let gate_inputs: Vec<bool> = self.gates[i].inputs.iter()
+					.map(|s| self.signal_value(*s, inputs, values))
+					.collect();
+
+				let value = self.gates[i].type_of.gate_output(&gate_inputs).unwrap_or_default();
+
+				values[i] = Some(value);
+
+				value
+			}
+		}
+	}
+}
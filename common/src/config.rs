@@ -1,41 +1,76 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
-use crate::{CellPos, Dimensions, NodeDirection, NodeObjectSide, NodeValueTypes};
+use crate::{CellPos, Dimensions, NodeDirection, NodeObjectSide, NodeValueTypes, Orientation};
 use crate::object::ObjectType;
 
+
+pub type PaletteColorJson = (u16, u16, u16);
+
+/// Palette a new Canvas starts with. (Wire Color, Active Wire Color)
+pub const DEFAULT_COLOR_PALETTE: [(PaletteColorJson, PaletteColorJson); 9] = [
+	((196, 196, 190), (145, 145, 134)),
+	((207, 175, 157), (173, 119, 88)),
+	((240, 183, 142), (228, 123, 47)),
+	((244, 216, 146), (234, 181, 45)),
+	((161, 208, 197), (92, 173, 154)),
+	((148, 193, 229), (63, 143, 209)),
+	((184, 156, 225), (125, 76, 200)),
+	((230, 147, 193), (210, 62, 143)),
+	((243, 143, 149), (233, 46, 58)),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConfigJson {
-	V1(CanvasStateJson)
+	/// Cell Positions are never negative.
+	V1(CanvasStateJson),
+	/// Cell Positions are signed.
+	V2(CanvasStateJson)
 }
 
 impl ConfigJson {
 	pub fn new(editor: CanvasStateJson) -> Self {
-		ConfigJson::V1(editor)
+		ConfigJson::V2(editor)
 	}
 
-	pub fn into_inner_json(self) -> CanvasStateJson {
+	/// Upgrades older saves to the current version.
+	///
+	/// V1 Cell Positions fit into the signed ones as they are.
+	#[must_use]
+	pub fn migrate(self) -> Self {
 		match self {
-			ConfigJson::V1(v) => v
+			ConfigJson::V1(v) => ConfigJson::V2(v),
+			v => v
+		}
+	}
+
+	pub fn into_inner_json(self) -> CanvasStateJson {
+		match self.migrate() {
+			ConfigJson::V1(v) |
+			ConfigJson::V2(v) => v
 		}
 	}
 
 	pub fn as_inner_json(&self) -> &CanvasStateJson {
 		match self {
-			ConfigJson::V1(v) => v
+			ConfigJson::V1(v) |
+			ConfigJson::V2(v) => v
 		}
 	}
 
 	pub fn as_mut_inner_json(&mut self) -> &mut CanvasStateJson {
 		match self {
-			ConfigJson::V1(v) => v
+			ConfigJson::V1(v) |
+			ConfigJson::V2(v) => v
 		}
 	}
 
 	pub fn editor_type(&self) -> EditorType {
 		match self {
-			ConfigJson::V1(_v) => EditorType::Canvas
+			ConfigJson::V1(_v) |
+			ConfigJson::V2(_v) => EditorType::Canvas
 		}
 	}
 }
@@ -191,7 +226,21 @@ pub struct CanvasStateJson {
 	/// palette index, Vec<CellPos>
 	pub pixels: HashMap<usize, Vec<CellPos>>,
 
-	pub text_objects: Vec<TextJson>
+	pub text_objects: Vec<TextJson>,
+
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub bookmarks: Vec<ViewBookmarkJson>
+}
+
+
+/// Saved view of the Canvas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewBookmarkJson {
+	pub name: String,
+
+	/// Cell in the middle of the view. Fractional so the view is restored exactly.
+	pub center: (f64, f64),
+	pub pixel_size: usize
 }
 
 
@@ -221,6 +270,30 @@ pub enum ObjectsJson {
 }
 
 impl ObjectsJson {
+	pub fn type_of(&self) -> ObjectType {
+		match self {
+			ObjectsJson::Normal(v) => v.type_of,
+			ObjectsJson::Custom(v) => v.type_of,
+			ObjectsJson::Reference(v) => v.type_of
+		}
+	}
+
+	pub fn pos(&self) -> CellPos {
+		match self {
+			ObjectsJson::Normal(v) => v.pos,
+			ObjectsJson::Custom(v) => v.pos,
+			ObjectsJson::Reference(v) => v.pos
+		}
+	}
+
+	pub fn nodes(&self) -> &[NodeJson] {
+		match self {
+			ObjectsJson::Normal(v) => &v.nodes,
+			ObjectsJson::Custom(v) => &v.nodes,
+			ObjectsJson::Reference(v) => &v.nodes
+		}
+	}
+
 	pub fn as_custom(&self) -> Option<&ObjectCustomJson> {
 		match self {
 			ObjectsJson::Custom(c) => Some(&*c),
@@ -258,7 +331,14 @@ pub struct ObjectNormalJson {
 	pub id: usize,
 	pub pos: CellPos,
 	pub dim: Dimensions,
-	pub nodes: Vec<NodeJson>
+	pub nodes: Vec<NodeJson>,
+
+	#[serde(default, skip_serializing_if = "Orientation::is_default")]
+	pub orientation: Orientation,
+
+	/// Saved settings of the Object. Ex: The Switch's default state.
+	#[serde(default, skip_serializing_if = "JsonValue::is_null")]
+	pub settings: JsonValue
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
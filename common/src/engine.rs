@@ -0,0 +1,182 @@
+use crate::config::{CanvasStateJson, ObjectsJson};
+use crate::netlist::Netlist;
+use crate::object::ObjectType;
+
+
+struct EngineObject {
+	type_of: ObjectType,
+
+	inputs: Vec<Option<usize>>,
+	outputs: Vec<Option<usize>>,
+
+	/// Switch/Button position or Clock level.
+	state: bool
+}
+
+
+/// Switch, Button, Clock or LED which can be referenced by name.
+struct Port {
+	name: String,
+	object: usize
+}
+
+
+/// Headless simulation of a Canvas.
+///
+/// Every tick each Object reads the nets on its inputs and drives its outputs, so a value moves through one Object per tick.
+/// Nets with more than one driver are high if any of them are.
+pub struct Engine {
+	netlist: Netlist,
+	objects: Vec<EngineObject>,
+	ports: Vec<Port>,
+
+	values: Vec<bool>,
+	ticks: usize
+}
+
+impl Engine {
+	/// Ports are named from their node labels. Unlabeled ones are named `#{object index}`.
+	pub fn new(canvas: &CanvasStateJson) -> Result<Self, String> {
+		let netlist = Netlist::new(canvas);
+
+		let mut objects = Vec::new();
+		let mut ports = Vec::new();
+
+		for (index, object) in canvas.objects.iter().enumerate() {
+			let json = match object {
+				ObjectsJson::Normal(v) => v,
+				_ => return Err(format!("Custom Objects aren't supported by the headless engine. Found one at {:?}", object.pos()))
+			};
+
+			let nets = |outputs: bool| -> Vec<Option<usize>> {
+				json.nodes.iter()
+					.enumerate()
+					.filter(|(_, n)| n.is_output() == outputs)
+					.map(|(i, _)| netlist.net_of_node((index, i)))
+					.collect()
+			};
+
+			if matches!(json.type_of, ObjectType::Switch | ObjectType::Button | ObjectType::Clock | ObjectType::Led) {
+				let name = json.nodes.iter()
+					.find_map(|n| n.label.clone())
+					.unwrap_or_else(|| format!("#{index}"));
+
+				if ports.iter().any(|p: &Port| p.name == name) {
+					return Err(format!("More than one Object is named `{name}`"));
+				}
+
+				ports.push(Port { name, object: index });
+			}
+
+			objects.push(EngineObject {
+				type_of: json.type_of,
+				inputs: nets(false),
+				outputs: nets(true),
+				state: false
+			});
+		}
+
+		Ok(Self {
+			values: vec![false; netlist.nets.len()],
+			netlist,
+			objects,
+			ports,
+
+			ticks: 0
+		})
+	}
+
+	fn port(&self, name: &str) -> Result<&Port, String> {
+		self.ports.iter()
+			.find(|p| p.name == name)
+			.ok_or_else(|| {
+				let names: Vec<&str> = self.ports.iter().map(|p| p.name.as_str()).collect();
+				format!("Nothing is named `{name}`. Available: {}", names.join(", "))
+			})
+	}
+
+	/// Sets a Switch or Button. Takes effect on the next tick.
+	pub fn set_input(&mut self, name: &str, value: bool) -> Result<(), String> {
+		let object = self.port(name)?.object;
+
+		let object = &mut self.objects[object];
+
+		if !matches!(object.type_of, ObjectType::Switch | ObjectType::Button) {
+			return Err(format!("`{name}` is a {:?} and can't be set", object.type_of));
+		}
+
+		object.state = value;
+
+		Ok(())
+	}
+
+	/// Value an LED is receiving.
+	pub fn output(&self, name: &str) -> Result<bool, String> {
+		let object = &self.objects[self.port(name)?.object];
+
+		if object.type_of != ObjectType::Led {
+			return Err(format!("`{name}` is a {:?} and not an LED", object.type_of));
+		}
+
+		Ok(object.inputs.iter().flatten().any(|&net| self.values[net]))
+	}
+
+	pub fn ticks(&self) -> usize {
+		self.ticks
+	}
+
+	pub fn tick(&mut self) {
+		let current = &self.values;
+		let mut values = vec![false; current.len()];
+
+		for object in &mut self.objects {
+			let inputs: Vec<bool> = object.inputs.iter()
+				.map(|net| net.is_some_and(|net| current[net]))
+				.collect();
+
+			let value = match object.type_of {
+				ObjectType::Switch | ObjectType::Button => object.state,
+
+				ObjectType::Clock => {
+					object.state = !object.state;
+					object.state
+				}
+
+				ObjectType::Led | ObjectType::TestObj => inputs.iter().any(|v| *v),
+
+				gate => gate.gate_output(&inputs).unwrap_or_default()
+			};
+
+			for net in object.outputs.iter().flatten() {
+				values[*net] |= value;
+			}
+		}
+
+		self.values = values;
+		self.ticks += 1;
+	}
+
+	/// Value of every net. Nets connected to a port take its name, the rest are named `n{index}`.
+	/// A port connected to more than one net names the others `{name}.{node index}`.
+	pub fn net_values(&self) -> Vec<(String, bool)> {
+		let mut named = Vec::new();
+
+		(0..self.netlist.nets.len())
+			.map(|net| {
+				let name = self.netlist.nets[net].nodes.iter()
+					.find_map(|&(object, node)| self.ports.iter().find(|p| p.object == object).map(|p| (p, node)))
+					.map_or_else(
+						|| format!("n{net}"),
+						|(port, node)| if named.contains(&port.object) {
+							format!("{}.{node}", port.name)
+						} else {
+							named.push(port.object);
+							port.name.clone()
+						}
+					);
+
+				(name, self.values[net])
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,503 @@
+use std::fmt::{self, Display, Formatter};
+
+
+/// A Boolean expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+	Const(bool),
+	Var(String),
+
+	Not(Box<Expr>),
+	And(Box<Expr>, Box<Expr>),
+	Or(Box<Expr>, Box<Expr>),
+	Xor(Box<Expr>, Box<Expr>)
+}
+
+impl Expr {
+	pub fn evaluate<F: Fn(&str) -> bool>(&self, lookup: &F) -> bool {
+		match self {
+			Self::Const(v) => *v,
+			Self::Var(name) => lookup(name),
+
+			Self::Not(v) => !v.evaluate(lookup),
+			Self::And(l, r) => l.evaluate(lookup) && r.evaluate(lookup),
+			Self::Or(l, r) => l.evaluate(lookup) || r.evaluate(lookup),
+			Self::Xor(l, r) => l.evaluate(lookup) != r.evaluate(lookup)
+		}
+	}
+
+	/// Appends the variable names in order of first appearance.
+	pub fn variables(&self, list: &mut Vec<String>) {
+		match self {
+			Self::Const(_) => (),
+
+			Self::Var(name) => if !list.contains(name) {
+				list.push(name.clone());
+			}
+
+			Self::Not(v) => v.variables(list),
+
+			Self::And(l, r) |
+			Self::Or(l, r) |
+			Self::Xor(l, r) => {
+				l.variables(list);
+				r.variables(list);
+			}
+		}
+	}
+
+	/// Folds constants and removes double negations.
+	pub fn simplify(self) -> Self {
+		match self {
+			Self::Not(v) => match v.simplify() {
+				Self::Const(v) => Self::Const(!v),
+				Self::Not(v) => *v,
+				v => Self::Not(Box::new(v))
+			},
+
+			Self::And(l, r) => match (l.simplify(), r.simplify()) {
+				(Self::Const(false), _) | (_, Self::Const(false)) => Self::Const(false),
+				(Self::Const(true), v) | (v, Self::Const(true)) => v,
+				(l, r) => Self::And(Box::new(l), Box::new(r))
+			},
+
+			Self::Or(l, r) => match (l.simplify(), r.simplify()) {
+				(Self::Const(true), _) | (_, Self::Const(true)) => Self::Const(true),
+				(Self::Const(false), v) | (v, Self::Const(false)) => v,
+				(l, r) => Self::Or(Box::new(l), Box::new(r))
+			},
+
+			Self::Xor(l, r) => match (l.simplify(), r.simplify()) {
+				(Self::Const(false), v) | (v, Self::Const(false)) => v,
+				(Self::Const(true), v) | (v, Self::Const(true)) => Self::Not(Box::new(v)).simplify(),
+				(l, r) => Self::Xor(Box::new(l), Box::new(r))
+			},
+
+			v => v
+		}
+	}
+}
+
+
+/// `output = expression`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+	pub output: String,
+	pub expr: Expr
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+	/// Starts at 1.
+	pub line: usize,
+	/// Starts at 1.
+	pub column: usize,
+
+	pub message: String
+}
+
+impl ParseError {
+	pub fn new<S: Into<String>>(line: usize, column: usize, message: S) -> Self {
+		Self { line, column, message: message.into() }
+	}
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "Line {}, Column {}: {}", self.line, self.column, self.message)
+	}
+}
+
+
+/// Parses either a list of assignments or a sum-of-products truth table.
+///
+/// Assignments are written one per line: `y = (a & !b) | c`. A line without `=` is assigned to `out`, `out1`, ..
+///
+/// A truth table starts with a header of inputs and outputs split by `|`, followed by one row per line.
+/// Inputs may use `x` or `-` as don't care.
+/// ```text
+/// a b c | y
+/// 0 0 1 | 1
+/// 1 - 0 | 1
+/// ```
+pub fn parse_assignments(value: &str) -> Result<Vec<Assignment>, ParseError> {
+	let lines: Vec<(usize, &str)> = value.lines()
+		.enumerate()
+		.map(|(i, l)| (i + 1, strip_comment(l).trim()))
+		.filter(|(_, l)| !l.is_empty())
+		.collect();
+
+	// `a | b` on its own is an expression. A table needs at least one row.
+	if let [(_, header), (_, row), ..] = lines.as_slice() {
+		if is_truth_table_header(header) && is_truth_table_row(row) {
+			return parse_truth_table(&lines);
+		}
+	}
+
+	let mut assignments = Vec::new();
+
+	for (line, text) in lines {
+		let (output, expr_text, offset) = if let Some(index) = text.find('=') {
+			let output = text[..index].trim();
+
+			if !is_identifier(output) {
+				return Err(ParseError::new(line, 1, format!("Invalid output name `{}`", output)));
+			}
+
+			(output.to_string(), &text[index + 1..], index + 1)
+		} else {
+			let output = if assignments.is_empty() {
+				String::from("out")
+			} else {
+				format!("out{}", assignments.len())
+			};
+
+			(output, text, 0)
+		};
+
+		if assignments.iter().any(|a: &Assignment| a.output == output) {
+			return Err(ParseError::new(line, 1, format!("Output `{}` is assigned twice", output)));
+		}
+
+		let expr = Parser::new(expr_text, line, offset).parse()?;
+
+		assignments.push(Assignment { output, expr });
+	}
+
+	if assignments.is_empty() {
+		return Err(ParseError::new(1, 1, "Nothing to parse"));
+	}
+
+	Ok(assignments)
+}
+
+/// Parses a single expression.
+pub fn parse_expression(value: &str) -> Result<Expr, ParseError> {
+	Parser::new(value, 1, 0).parse()
+}
+
+
+fn strip_comment(line: &str) -> &str {
+	let end = [line.find('#'), line.find("//")].iter()
+		.filter_map(|v| *v)
+		.min()
+		.unwrap_or(line.len());
+
+	&line[..end]
+}
+
+fn is_identifier(value: &str) -> bool {
+	let mut chars = value.chars();
+
+	chars.next().map(|c| c.is_ascii_alphabetic() || c == '_').unwrap_or_default() &&
+	chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_truth_table_header(line: &str) -> bool {
+	let mut split = line.split('|');
+
+	match (split.next(), split.next(), split.next()) {
+		(Some(inputs), Some(outputs), None) => {
+			let inputs: Vec<&str> = inputs.split_whitespace().collect();
+			let outputs: Vec<&str> = outputs.split_whitespace().collect();
+
+			!inputs.is_empty() && !outputs.is_empty() &&
+			inputs.iter().chain(outputs.iter()).all(|v| is_identifier(v))
+		}
+
+		_ => false
+	}
+}
+
+fn is_truth_table_row(line: &str) -> bool {
+	let mut split = line.split('|');
+
+	match (split.next(), split.next(), split.next()) {
+		(Some(inputs), Some(outputs), None) => {
+			let inputs: Vec<&str> = inputs.split_whitespace().collect();
+			let outputs: Vec<&str> = outputs.split_whitespace().collect();
+
+			!inputs.is_empty() && !outputs.is_empty() &&
+			inputs.iter().chain(outputs.iter()).all(|v| matches!(*v, "0" | "1" | "x" | "X" | "-"))
+		}
+
+		_ => false
+	}
+}
+
+fn parse_truth_table(lines: &[(usize, &str)]) -> Result<Vec<Assignment>, ParseError> {
+	let mut split = lines[0].1.split('|');
+
+	let inputs: Vec<&str> = split.next().unwrap().split_whitespace().collect();
+	let outputs: Vec<&str> = split.next().unwrap().split_whitespace().collect();
+
+	let mut products: Vec<Option<Expr>> = vec![None; outputs.len()];
+
+	for &(line, text) in &lines[1..] {
+		let mut split = text.split('|');
+
+		let (row_in, row_out) = match (split.next(), split.next(), split.next()) {
+			(Some(i), Some(o), None) => (
+				i.split_whitespace().collect::<Vec<_>>(),
+				o.split_whitespace().collect::<Vec<_>>()
+			),
+			_ => return Err(ParseError::new(line, 1, "Expected a row of `inputs | outputs`"))
+		};
+
+		if row_in.len() != inputs.len() || row_out.len() != outputs.len() {
+			return Err(ParseError::new(line, 1, format!("Expected {} inputs and {} outputs", inputs.len(), outputs.len())));
+		}
+
+		// Minterm of the row.
+		let mut term: Option<Expr> = None;
+
+		for (name, value) in inputs.iter().zip(row_in) {
+			let literal = match value {
+				"1" => Expr::Var(name.to_string()),
+				"0" => Expr::Not(Box::new(Expr::Var(name.to_string()))),
+				"x" | "X" | "-" => continue,
+				v => return Err(ParseError::new(line, 1, format!("Unexpected input value `{}`", v)))
+			};
+
+			term = Some(match term {
+				Some(t) => Expr::And(Box::new(t), Box::new(literal)),
+				None => literal
+			});
+		}
+
+		let term = term.unwrap_or(Expr::Const(true));
+
+		for (product, value) in products.iter_mut().zip(row_out) {
+			match value {
+				"1" => {
+					*product = Some(match product.take() {
+						Some(p) => Expr::Or(Box::new(p), Box::new(term.clone())),
+						None => term.clone()
+					});
+				}
+
+				"0" | "x" | "X" | "-" => (),
+				v => return Err(ParseError::new(line, 1, format!("Unexpected output value `{}`", v)))
+			}
+		}
+	}
+
+	Ok(
+		outputs.into_iter()
+			.zip(products)
+			.map(|(output, expr)| Assignment {
+				output: output.to_string(),
+				expr: expr.unwrap_or(Expr::Const(false))
+			})
+			.collect()
+	)
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Ident(String),
+	Const(bool),
+
+	Not,
+	And,
+	Or,
+	Xor,
+
+	Open,
+	Close
+}
+
+
+struct Parser {
+	tokens: Vec<(usize, Token)>,
+	index: usize,
+
+	line: usize,
+	end_column: usize
+}
+
+impl Parser {
+	fn new(value: &str, line: usize, offset: usize) -> Self {
+		let mut tokens = Vec::new();
+		let mut chars = value.char_indices().peekable();
+
+		while let Some((i, c)) = chars.next() {
+			let column = offset + i + 1;
+
+			let token = match c {
+				'!' | '~' => Token::Not,
+				'&' | '*' => Token::And,
+				'|' | '+' => Token::Or,
+				'^' => Token::Xor,
+				'(' => Token::Open,
+				')' => Token::Close,
+				'0' => Token::Const(false),
+				'1' => Token::Const(true),
+
+				c if c.is_ascii_alphabetic() || c == '_' => {
+					let mut name = c.to_string();
+
+					while let Some(&(_, c)) = chars.peek() {
+						if c.is_ascii_alphanumeric() || c == '_' {
+							name.push(c);
+							chars.next();
+						} else {
+							break;
+						}
+					}
+
+					Token::Ident(name)
+				}
+
+				c if c.is_whitespace() => continue,
+
+				// Unknown characters are kept as an identifier so the error points at them.
+				c => Token::Ident(format!("\0{}", c))
+			};
+
+			tokens.push((column, token));
+		}
+
+		Self {
+			tokens,
+			index: 0,
+			line,
+			end_column: offset + value.len() + 1
+		}
+	}
+
+	fn parse(mut self) -> Result<Expr, ParseError> {
+		let expr = self.parse_or()?;
+
+		if let Some((column, _)) = self.tokens.get(self.index) {
+			return Err(ParseError::new(self.line, *column, "Unexpected token"));
+		}
+
+		Ok(expr)
+	}
+
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.index).map(|v| &v.1)
+	}
+
+	fn column(&self) -> usize {
+		self.tokens.get(self.index).map_or(self.end_column, |v| v.0)
+	}
+
+	fn parse_or(&mut self) -> Result<Expr, ParseError> {
+		let mut expr = self.parse_xor()?;
+
+		while self.peek() == Some(&Token::Or) {
+			self.index += 1;
+			expr = Expr::Or(Box::new(expr), Box::new(self.parse_xor()?));
+		}
+
+		Ok(expr)
+	}
+
+	fn parse_xor(&mut self) -> Result<Expr, ParseError> {
+		let mut expr = self.parse_and()?;
+
+		while self.peek() == Some(&Token::Xor) {
+			self.index += 1;
+			expr = Expr::Xor(Box::new(expr), Box::new(self.parse_and()?));
+		}
+
+		Ok(expr)
+	}
+
+	fn parse_and(&mut self) -> Result<Expr, ParseError> {
+		let mut expr = self.parse_unary()?;
+
+		while self.peek() == Some(&Token::And) {
+			self.index += 1;
+			expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+		}
+
+		Ok(expr)
+	}
+
+	fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+		let column = self.column();
+
+		match self.tokens.get(self.index).map(|v| v.1.clone()) {
+			Some(Token::Not) => {
+				self.index += 1;
+				Ok(Expr::Not(Box::new(self.parse_unary()?)))
+			}
+
+			Some(Token::Open) => {
+				self.index += 1;
+
+				let expr = self.parse_or()?;
+
+				if self.peek() == Some(&Token::Close) {
+					self.index += 1;
+					Ok(expr)
+				} else {
+					Err(ParseError::new(self.line, self.column(), "Expected `)`"))
+				}
+			}
+
+			Some(Token::Const(v)) => {
+				self.index += 1;
+				Ok(Expr::Const(v))
+			}
+
+			Some(Token::Ident(name)) if !name.starts_with('\0') => {
+				self.index += 1;
+				Ok(Expr::Var(name))
+			}
+
+			Some(Token::Ident(name)) => Err(ParseError::new(self.line, column, format!("Unexpected character `{}`", &name[1..]))),
+
+			Some(_) => Err(ParseError::new(self.line, column, "Unexpected token")),
+
+			None => Err(ParseError::new(self.line, column, "Unexpected end of expression"))
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn or(l: &str, r: &str) -> Expr {
+		Expr::Or(Box::new(Expr::Var(l.to_string())), Box::new(Expr::Var(r.to_string())))
+	}
+
+	#[test]
+	fn or_without_output_is_an_expression() {
+		for value in ["a | b", "a|b"] {
+			let assignments = parse_assignments(value).unwrap();
+
+			assert_eq!(assignments.len(), 1);
+			assert_eq!(assignments[0].output, "out");
+			assert_eq!(assignments[0].expr, or("a", "b"));
+		}
+	}
+
+	#[test]
+	fn or_with_output_is_an_expression() {
+		let assignments = parse_assignments("y = a | b").unwrap();
+
+		assert_eq!(assignments.len(), 1);
+		assert_eq!(assignments[0].output, "y");
+		assert_eq!(assignments[0].expr, or("a", "b"));
+	}
+
+	#[test]
+	fn truth_table_with_rows() {
+		let assignments = parse_assignments("a b | y\n0 1 | 1\n1 0 | 1\n1 1 | 0").unwrap();
+
+		assert_eq!(assignments.len(), 1);
+		assert_eq!(assignments[0].output, "y");
+
+		for (a, b, y) in [(false, false, false), (false, true, true), (true, false, true), (true, true, false)] {
+			assert_eq!(assignments[0].expr.evaluate(&|name| if name == "a" { a } else { b }), y);
+		}
+	}
+}
@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+
+use crate::{CellPos, Dimensions, Orientation};
+use crate::circuit::{Circuit, Signal};
+use crate::config::{CanvasStateJson, NodeJson, ObjectNormalJson, ObjectsJson, DEFAULT_COLOR_PALETTE};
+use crate::object::ObjectType;
+use crate::route::{self, RouteBounds};
+
+
+/// Empty cells around the placed Objects. Gives the router room to go around everything.
+const MARGIN: usize = 8;
+/// Space between two columns before accounting for the wires running through it.
+const COLUMN_GAP: usize = 4;
+/// Space between two Objects in a column before accounting for the wires running through it.
+const ROW_GAP: usize = 6;
+/// Placements of gate inputs to try before giving up on routing.
+const ROUTE_ATTEMPTS: usize = 64;
+
+
+struct Item {
+	type_of: ObjectType,
+	column: usize,
+
+	pos: CellPos,
+	dim: Dimensions,
+
+	nodes: Vec<NodeJson>
+}
+
+impl Item {
+	fn new(type_of: ObjectType, column: usize, label: Option<&str>) -> Self {
+		let nodes = type_of.default_nodes()
+			.into_iter()
+			.map(|(side, direction, accepts)| {
+				// Only the node which is wired up is labeled.
+				let is_wired = match type_of {
+					ObjectType::Switch => side.is_right(),
+					ObjectType::Led => direction.is_input(),
+					_ => false
+				};
+
+				NodeJson {
+					pos: (0, 0),
+					side,
+					direction,
+					accepts,
+					label: label.filter(|_| is_wired).map(String::from),
+					is_disabled: false
+				}
+			})
+			.collect();
+
+		Self {
+			type_of,
+			column,
+			pos: (0, 0),
+			dim: type_of.default_dimensions(),
+			nodes
+		}
+	}
+
+	fn set_pos(&mut self, pos: CellPos) {
+		self.pos = pos;
+
+		for node in &mut self.nodes {
+			node.pos = node.side.get_cell_pos(&self.dim, pos);
+		}
+	}
+
+	fn center_y(&self) -> usize {
+		self.pos.1 as usize + self.dim.height() / 2
+	}
+
+	fn input_count(&self) -> usize {
+		self.nodes.iter().filter(|v| v.is_input()).count()
+	}
+
+	/// Node indexes a net can be driven from. Switches can be wired from both sides.
+	fn output_nodes(&self) -> Vec<usize> {
+		self.nodes.iter()
+			.enumerate()
+			.filter(|(_, v)| v.is_output() && (self.type_of == ObjectType::Switch || v.side.is_right()))
+			.map(|(i, _)| i)
+			.collect()
+	}
+}
+
+
+struct Net {
+	name: String,
+	palette_index: usize,
+
+	driver: usize,
+	/// (item, n-th input)
+	sinks: Vec<(usize, usize)>
+}
+
+
+#[derive(Clone, Copy, PartialEq)]
+enum Occupant {
+	Object,
+	/// (item, node)
+	Node(usize, usize),
+	/// Net index
+	Wire(usize)
+}
+
+
+/// Places the gate network in logic levels from left to right and routes every net with its own palette color.
+///
+/// Inputs become Switches and outputs become LEDs. Both are labeled on the wired node.
+pub fn layout_circuit(circuit: &Circuit) -> Result<CanvasStateJson, String> {
+	let levels = circuit.gate_levels();
+	let output_column = levels.iter().copied().max().unwrap_or_default() + 1;
+
+	let mut items: Vec<Item> = Vec::new();
+
+	for name in &circuit.inputs {
+		items.push(Item::new(ObjectType::Switch, 0, Some(name)));
+	}
+
+	for (gate, &level) in circuit.gates.iter().zip(&levels) {
+		items.push(Item::new(gate.type_of, level, None));
+	}
+
+	for (name, _) in &circuit.outputs {
+		items.push(Item::new(ObjectType::Led, output_column, Some(name)));
+	}
+
+	let signal_item = |signal: Signal| match signal {
+		Signal::Input(i) => i,
+		Signal::Gate(i) => circuit.inputs.len() + i
+	};
+
+	// Nets. One per driving signal.
+	let mut nets: Vec<Net> = Vec::new();
+	let mut net_by_driver: HashMap<usize, usize> = HashMap::new();
+
+	let sinks = circuit.gates.iter()
+		.enumerate()
+		.flat_map(|(i, g)| g.inputs.iter().enumerate().map(move |(n, s)| (*s, circuit.inputs.len() + i, n)))
+		.chain(circuit.outputs.iter().enumerate().map(|(i, (_, s))| (*s, circuit.inputs.len() + circuit.gates.len() + i, 0)));
+
+	for (signal, sink_item, input) in sinks {
+		let driver_item = signal_item(signal);
+
+		let net_index = *net_by_driver.entry(driver_item).or_insert_with(|| {
+			nets.push(Net {
+				name: match signal {
+					Signal::Input(i) => circuit.inputs[i].clone(),
+					Signal::Gate(i) => circuit.outputs.iter()
+						.find(|(_, s)| *s == signal)
+						.map_or_else(|| format!("{:?} #{}", circuit.gates[i].type_of, i), |(n, _)| n.clone())
+				},
+				palette_index: nets.len() % DEFAULT_COLOR_PALETTE.len(),
+				driver: driver_item,
+				sinks: Vec::new()
+			});
+
+			nets.len() - 1
+		});
+
+		nets[net_index].sinks.push((sink_item, input));
+	}
+
+	place_items(&mut items, &nets, output_column);
+
+	let pixels = route_nets(&items, &nets)?;
+
+	Ok(CanvasStateJson {
+		objects: items.into_iter()
+			.enumerate()
+			.map(|(i, item)| ObjectsJson::Normal(ObjectNormalJson {
+				type_of: item.type_of,
+				id: i + 1,
+				pos: item.pos,
+				dim: item.dim,
+				nodes: item.nodes,
+				orientation: Orientation::default(),
+				settings: Default::default()
+			}))
+			.collect(),
+
+		color_palette: DEFAULT_COLOR_PALETTE.iter()
+			.flat_map(|&(p1, p2)| vec![p1, p2])
+			.collect(),
+
+		pixels,
+
+		text_objects: Vec::new(),
+		bookmarks: Vec::new()
+	})
+}
+
+
+fn place_items(items: &mut [Item], nets: &[Net], output_column: usize) {
+	let column_count = output_column + 1;
+
+	// Amount of nets running through each column and each gap after a column.
+	let mut passing = vec![0; column_count];
+	let mut crossing = vec![0; column_count];
+
+	for net in nets {
+		let start = items[net.driver].column;
+		let end = net.sinks.iter().map(|s| items[s.0].column).max().unwrap_or(start);
+
+		(start + 1..end).for_each(|c| passing[c] += 1);
+		(start..end).for_each(|c| crossing[c] += 1);
+	}
+
+	let mut pos_x = MARGIN;
+
+	for column in 0..column_count {
+		let mut indexes: Vec<usize> = (0..items.len()).filter(|i| items[*i].column == column).collect();
+
+		// Wanted height of an item is the average height of what drives it.
+		let wanted_y: HashMap<usize, usize> = indexes.iter()
+			.map(|&i| {
+				let drivers: Vec<usize> = nets.iter()
+					.filter(|n| n.sinks.iter().any(|s| s.0 == i))
+					.map(|n| items[n.driver].center_y())
+					.collect();
+
+				let wanted = if drivers.is_empty() {
+					0
+				} else {
+					drivers.iter().sum::<usize>() / drivers.len()
+				};
+
+				(i, wanted)
+			})
+			.collect();
+
+		if column != 0 {
+			indexes.sort_by_key(|i| wanted_y[i]);
+		}
+
+		let row_gap = ROW_GAP + passing[column];
+
+		let mut pos_y = MARGIN;
+
+		for i in indexes {
+			let item = &mut items[i];
+
+			let half = item.dim.height() / 2;
+			let y = pos_y.max(wanted_y[&i].saturating_sub(half));
+
+			item.set_pos((pos_x as i32, y as i32));
+
+			pos_y = y + item.dim.height() + row_gap;
+		}
+
+		let width = items.iter()
+			.filter(|v| v.column == column)
+			.map(|v| v.dim.width())
+			.max()
+			.unwrap_or(1);
+
+		pos_x += width + COLUMN_GAP + crossing[column] * 2;
+	}
+}
+
+
+fn route_nets(items: &[Item], nets: &[Net]) -> Result<HashMap<usize, Vec<CellPos>>, String> {
+	let mut base: HashMap<CellPos, Occupant> = HashMap::new();
+
+	for (index, item) in items.iter().enumerate() {
+		for x in 0..item.dim.width() {
+			for y in 0..item.dim.height() {
+				base.insert((item.pos.0 + x as i32, item.pos.1 + y as i32), Occupant::Object);
+			}
+		}
+
+		for (node_index, node) in item.nodes.iter().enumerate() {
+			base.insert(node.pos, Occupant::Node(index, node_index));
+		}
+	}
+
+	let max = base.keys().fold((0, 0), |max, c| (max.0.max(c.0), max.1.max(c.1)));
+	let bounds = RouteBounds::new((0, 0), (max.0 + MARGIN as i32, max.1 + MARGIN as i32));
+
+	// Shortest nets first. A net which fails to route is moved to the front and everything is routed again.
+	let mut order: Vec<usize> = (0..nets.len()).collect();
+	order.sort_by_key(|&i| {
+		let start = items[nets[i].driver].pos;
+		nets[i].sinks.iter().map(|s| route::distance(start, items[s.0].pos)).max().unwrap_or_default()
+	});
+
+	// Every gate is commutative so the inputs can be wired to any of its input nodes.
+	let mut pins: Vec<Vec<usize>> = items.iter()
+		.map(|item| item.nodes.iter().enumerate().filter(|(_, v)| v.is_input()).map(|(i, _)| i).collect())
+		.collect();
+
+	let mut seed: u32 = 0x2545_F491;
+	let mut failed = 0;
+
+	for attempt in 0..ROUTE_ATTEMPTS {
+		if attempt != 0 {
+			for (item, pins) in items.iter().zip(&mut pins) {
+				// Xorshift. Only needs to be different between attempts.
+				seed ^= seed << 13;
+				seed ^= seed >> 17;
+				seed ^= seed << 5;
+
+				if item.input_count() == 2 && seed & 1 == 0 {
+					pins.swap(0, 1);
+				}
+			}
+		}
+
+		let mut node_nets: HashMap<(usize, usize), usize> = HashMap::new();
+
+		for (index, net) in nets.iter().enumerate() {
+			items[net.driver].output_nodes().into_iter().for_each(|n| { node_nets.insert((net.driver, n), index); });
+			net.sinks.iter().for_each(|&(item, n)| { node_nets.insert((item, pins[item][n]), index); });
+		}
+
+		let mut grid = base.clone();
+		let mut routed: Vec<Vec<CellPos>> = vec![Vec::new(); nets.len()];
+
+		let failed_net = order.iter().copied().find(|&net_index| {
+			match route_net(items, nets, net_index, &pins, &mut grid, &node_nets, bounds) {
+				Some(cells) => {
+					routed[net_index] = cells;
+					false
+				}
+
+				None => true
+			}
+		});
+
+		match failed_net {
+			None => {
+				let mut pixels: HashMap<usize, Vec<CellPos>> = HashMap::new();
+
+				for (net, cells) in nets.iter().zip(routed) {
+					pixels.entry(net.palette_index).or_default().extend(cells);
+				}
+
+				return Ok(pixels);
+			}
+
+			Some(net_index) => {
+				failed = net_index;
+
+				order.retain(|v| *v != net_index);
+				order.insert(0, net_index);
+			}
+		}
+	}
+
+	Err(format!("Unable to route `{}` without crossing another wire", nets[failed].name))
+}
+
+fn route_net(
+	items: &[Item],
+	nets: &[Net],
+	net_index: usize,
+	pins: &[Vec<usize>],
+	grid: &mut HashMap<CellPos, Occupant>,
+	node_nets: &HashMap<(usize, usize), usize>,
+	bounds: RouteBounds
+) -> Option<Vec<CellPos>> {
+	let net = &nets[net_index];
+
+	let is_passable = |grid: &HashMap<CellPos, Occupant>, cell: CellPos| -> bool {
+		if grid.contains_key(&cell) {
+			return false;
+		}
+
+		// Never touch a node of another net or a same colored wire of another net.
+		neighbours(cell).iter().flatten().all(|n| match grid.get(n) {
+			Some(Occupant::Node(item, node)) => node_nets.get(&(*item, *node)) == Some(&net_index),
+			Some(Occupant::Wire(other)) => *other == net_index || nets[*other].palette_index != net.palette_index,
+			_ => true
+		})
+	};
+
+	let facing = |item: usize, node: usize| -> CellPos {
+		let node = &items[item].nodes[node];
+		node.side.as_facing_cell_pos(node.pos)
+	};
+
+	let starts: Vec<CellPos> = items[net.driver].output_nodes()
+		.into_iter()
+		.map(|n| facing(net.driver, n))
+		.filter(|c| is_passable(grid, *c))
+		.collect();
+
+	if starts.is_empty() {
+		return None;
+	}
+
+	let mut sinks: Vec<CellPos> = net.sinks.iter().map(|&(item, n)| facing(item, pins[item][n])).collect();
+	sinks.sort_by_key(|c| starts.iter().map(|s| route::distance(*s, *c)).min());
+
+	let mut cells: Vec<CellPos> = Vec::new();
+
+	for goal in sinks {
+		if cells.contains(&goal) {
+			continue;
+		}
+
+		// Continue from the already routed wire or from an unused output.
+		let from: Vec<CellPos> = cells.iter().chain(starts.iter().filter(|c| !cells.contains(c))).copied().collect();
+
+		let path = route::find_path(&from, goal, bounds, |c| is_passable(grid, c))?;
+
+		for cell in path {
+			if !cells.contains(&cell) {
+				grid.insert(cell, Occupant::Wire(net_index));
+				cells.push(cell);
+			}
+		}
+	}
+
+	Some(cells)
+}
+
+fn neighbours((x, y): CellPos) -> [Option<CellPos>; 4] {
+	[
+		y.checked_sub(1).map(|y| (x, y)),
+		x.checked_sub(1).map(|x| (x, y)),
+		Some((x + 1, y)),
+		Some((x, y + 1))
+	]
+}
@@ -1,10 +1,20 @@
-pub type CellPos = (usize, usize);
+/// Signed so the Canvas can grow in every direction.
+pub type CellPos = (i32, i32);
 pub type CanvasPos = (f64, f64);
 
 pub mod config;
 pub mod object;
 pub mod size;
 pub mod http;
+pub mod expr;
+pub mod circuit;
+pub mod route;
+pub mod layout;
+pub mod netlist;
+pub mod timing;
+pub mod engine;
+pub mod testbench;
+pub mod verilog;
 
 pub use object::{NodeValueTypes, MARGIN_SIZE};
 pub use size::*;
\ No newline at end of file
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use crate::{CellPos, NodeObjectSide};
+use crate::config::{CanvasStateJson, NodeJson, ObjectsJson};
+
+
+/// Node of an Object. (Object Index, Node Index)
+pub type NodeRef = (usize, usize);
+
+
+/// Everything which is electrically connected together.
+#[derive(Debug, Clone, Default)]
+pub struct Net {
+	pub nodes: Vec<NodeRef>,
+	pub wires: Vec<CellPos>,
+
+	/// Palette indexes of the wires.
+	pub palette_indexes: Vec<usize>
+}
+
+
+/// Connectivity of a Canvas.
+///
+/// Wires connect to their neighbours of the same color. Nodes connect to the wires and input nodes on their side.
+/// Every enabled node is in a net, even if nothing is connected to it.
+#[derive(Debug, Clone, Default)]
+pub struct Netlist {
+	pub nets: Vec<Net>,
+
+	node_nets: HashMap<NodeRef, usize>,
+	wire_nets: HashMap<CellPos, usize>
+}
+
+impl Netlist {
+	pub fn new(canvas: &CanvasStateJson) -> Self {
+		Self::new_with_ports(canvas, &[])
+	}
+
+	/// `ports` are extra nodes which don't belong to an Object. They're referenced as `(canvas.objects.len(), index)`.
+	pub fn new_with_ports(canvas: &CanvasStateJson, ports: &[NodeJson]) -> Self {
+		let wires: Vec<(CellPos, usize)> = canvas.pixels.iter()
+			.flat_map(|(index, cells)| cells.iter().map(move |c| (*c, *index)))
+			.collect();
+
+		let nodes: Vec<(NodeRef, &NodeJson)> = canvas.objects.iter()
+			.map(ObjectsJson::nodes)
+			.chain(std::iter::once(ports))
+			.enumerate()
+			.flat_map(|(object, nodes)| nodes.iter().enumerate().map(move |(i, n)| ((object, i), n)))
+			.collect();
+
+		let color = |index: usize| canvas.color_palette.get(index * 2).copied();
+
+		Self::from_parts(wires, &nodes, |a, b| color(a) == color(b))
+	}
+
+	/// Builds the netlist from wire cells with their palette index and the nodes of every Object.
+	///
+	/// `same_color` tells if two palette indexes are the same wire color.
+	pub fn from_parts<F>(mut wires: Vec<(CellPos, usize)>, nodes: &[(NodeRef, &NodeJson)], same_color: F) -> Self where F: Fn(usize, usize) -> bool {
+		// Keeps the net order the same between runs.
+		wires.sort_unstable();
+
+		let nodes: Vec<(NodeRef, &NodeJson)> = nodes.iter()
+			.copied()
+			.filter(|(_, n)| !n.is_disabled)
+			.collect();
+
+		let wire_indexes: HashMap<CellPos, usize> = wires.iter()
+			.enumerate()
+			.map(|(i, (cell, _))| (*cell, i))
+			.collect();
+
+		// Wires first, then nodes.
+		let mut sets = DisjointSet::new(wires.len() + nodes.len());
+
+		for (i, &(cell, index)) in wires.iter().enumerate() {
+			for next in [(cell.0 + 1, cell.1), (cell.0, cell.1 + 1)] {
+				if let Some(&other) = wire_indexes.get(&next) {
+					if same_color(wires[other].1, index) {
+						sets.union(i, other);
+					}
+				}
+			}
+		}
+
+		let mut nodes_in_cells: HashMap<CellPos, Vec<usize>> = HashMap::new();
+
+		for (i, (_, node)) in nodes.iter().enumerate() {
+			nodes_in_cells.entry(node.pos).or_default().push(wires.len() + i);
+		}
+
+		for (i, (_, node)) in nodes.iter().enumerate() {
+			for cell in node.side.get_surrounding_cells(node.pos).iter().flatten() {
+				if let Some(&wire) = wire_indexes.get(cell) {
+					sets.union(wires.len() + i, wire);
+				}
+
+				// Output directly into another Input.
+				if node.is_output() {
+					for &other in nodes_in_cells.get(cell).into_iter().flatten() {
+						if nodes[other - wires.len()].1.is_input() {
+							sets.union(wires.len() + i, other);
+						}
+					}
+				}
+			}
+		}
+
+		let mut this = Self::default();
+		let mut root_nets: HashMap<usize, usize> = HashMap::new();
+
+		for (i, (node, _)) in nodes.iter().enumerate() {
+			let net = this.net_for_root(&mut root_nets, sets.find(wires.len() + i));
+
+			this.nets[net].nodes.push(*node);
+			this.node_nets.insert(*node, net);
+		}
+
+		for (i, &(cell, index)) in wires.iter().enumerate() {
+			let net = this.net_for_root(&mut root_nets, sets.find(i));
+
+			let net_item = &mut this.nets[net];
+
+			net_item.wires.push(cell);
+
+			if !net_item.palette_indexes.contains(&index) {
+				net_item.palette_indexes.push(index);
+			}
+
+			this.wire_nets.insert(cell, net);
+		}
+
+		this
+	}
+
+	fn net_for_root(&mut self, root_nets: &mut HashMap<usize, usize>, root: usize) -> usize {
+		let nets = &mut self.nets;
+
+		*root_nets.entry(root).or_insert_with(|| {
+			nets.push(Net::default());
+			nets.len() - 1
+		})
+	}
+
+
+	pub fn net_of_node(&self, node: NodeRef) -> Option<usize> {
+		self.node_nets.get(&node).copied()
+	}
+
+	pub fn net_of_wire(&self, cell: CellPos) -> Option<usize> {
+		self.wire_nets.get(&cell).copied()
+	}
+
+	/// Net which is connected to the node on `side` of the Object.
+	pub fn net_of_side(&self, canvas: &CanvasStateJson, object: usize, side: NodeObjectSide) -> Option<usize> {
+		let node = canvas.objects.get(object)?.nodes().iter().position(|n| n.side == side)?;
+
+		self.net_of_node((object, node))
+	}
+}
+
+
+/// Union-Find
+struct DisjointSet {
+	parents: Vec<usize>
+}
+
+impl DisjointSet {
+	fn new(size: usize) -> Self {
+		Self {
+			parents: (0..size).collect()
+		}
+	}
+
+	fn find(&mut self, mut index: usize) -> usize {
+		while self.parents[index] != index {
+			self.parents[index] = self.parents[self.parents[index]];
+			index = self.parents[index];
+		}
+
+		index
+	}
+
+	fn union(&mut self, a: usize, b: usize) {
+		let (a, b) = (self.find(a), self.find(b));
+
+		if a != b {
+			self.parents[b] = a;
+		}
+	}
+}
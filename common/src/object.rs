@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{Dimensions, NodeDirection, NodeObjectSide};
+
 pub const MARGIN_SIZE: f64 = 3.0;
 
 
@@ -45,7 +47,7 @@ impl PartialEq for NodeValueTypes {
 	}
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum ObjectType {
 	Button,
 	Clock,
@@ -68,12 +70,100 @@ impl ObjectType {
 			ObjectType::Led,
 			ObjectType::Switch,
 
-			// ObjectType::AndGate,
-			// ObjectType::NotGate,
-			// ObjectType::OrGate,
-			// ObjectType::XorGate,
+			ObjectType::AndGate,
+			ObjectType::NotGate,
+			ObjectType::OrGate,
+			ObjectType::XorGate,
 
 			ObjectType::TestObj
 		]
 	}
+
+	pub fn is_gate(self) -> bool {
+		matches!(self, Self::AndGate | Self::NotGate | Self::OrGate | Self::XorGate)
+	}
+
+	/// Returns the output of a gate from its input values. `None` if the Object isn't a gate.
+	pub fn gate_output(self, inputs: &[bool]) -> Option<bool> {
+		Some(match self {
+			Self::AndGate => inputs.iter().all(|v| *v),
+			Self::OrGate => inputs.iter().any(|v| *v),
+			Self::XorGate => inputs.iter().filter(|v| **v).count() % 2 == 1,
+			Self::NotGate => !inputs.iter().any(|v| *v),
+
+			_ => return None
+		})
+	}
+
+	/// Time in nanoseconds from an input changing to the output changing.
+	pub fn propagation_delay(self) -> f64 {
+		match self {
+			Self::NotGate => 1.0,
+			Self::AndGate | Self::OrGate => 2.0,
+			Self::XorGate => 3.0,
+			Self::TestObj => 1.0,
+
+			Self::Button |
+			Self::Clock |
+			Self::Led |
+			Self::Switch => 0.0
+		}
+	}
+
+	/// Size the Object is created with.
+	pub fn default_dimensions(self) -> Dimensions {
+		match self {
+			Self::Button |
+			Self::Clock |
+			Self::Led |
+			Self::Switch => Dimensions(1, 1),
+
+			Self::AndGate |
+			Self::NotGate |
+			Self::OrGate |
+			Self::XorGate |
+			Self::TestObj => Dimensions(3, 3)
+		}
+	}
+
+	/// Nodes the Object is created with.
+	///
+	/// Gates take their inputs on the left side and output on the right side.
+	pub fn default_nodes(self) -> Vec<(NodeObjectSide, NodeDirection, NodeValueTypes)> {
+		use NodeDirection::{Input, Output};
+
+		match self {
+			Self::Button => vec![
+				(NodeObjectSide::Right(0), Output, NodeValueTypes::Gpio),
+				(NodeObjectSide::Left(0), Output, NodeValueTypes::Gpio),
+				(NodeObjectSide::Top(0), Output, NodeValueTypes::Gpio),
+				(NodeObjectSide::Bottom(0), Output, NodeValueTypes::Gpio)
+			],
+
+			Self::Clock |
+			Self::Switch => vec![
+				(NodeObjectSide::Left(0), Output, NodeValueTypes::Gpio),
+				(NodeObjectSide::Right(0), Output, NodeValueTypes::Gpio)
+			],
+
+			Self::Led => vec![
+				(NodeObjectSide::Left(0), Input, NodeValueTypes::GpioOrCurrent),
+				(NodeObjectSide::Right(0), Output, NodeValueTypes::GpioOrCurrent)
+			],
+
+			Self::AndGate |
+			Self::OrGate |
+			Self::XorGate => vec![
+				(NodeObjectSide::Left(2), Input, NodeValueTypes::Gpio),
+				(NodeObjectSide::Left(0), Input, NodeValueTypes::Gpio),
+				(NodeObjectSide::Right(1), Output, NodeValueTypes::Gpio)
+			],
+
+			Self::NotGate |
+			Self::TestObj => vec![
+				(NodeObjectSide::Left(1), Input, NodeValueTypes::Gpio),
+				(NodeObjectSide::Right(1), Output, NodeValueTypes::Gpio)
+			]
+		}
+	}
 }
\ No newline at end of file
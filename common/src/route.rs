@@ -0,0 +1,118 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::{CellPos, Side};
+
+
+/// Extra cost of changing direction. Makes the router prefer fewer bends over a slightly shorter path.
+pub const BEND_COST: usize = 3;
+
+const DIRECTIONS: [Side; 4] = [Side::Top, Side::Right, Side::Bottom, Side::Left];
+
+/// (Cell, Direction Index) -> (Cost, Previous)
+type Visited = HashMap<(CellPos, usize), (usize, Option<(CellPos, usize)>)>;
+
+
+/// Area the router is allowed to search in. Both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RouteBounds {
+	pub min: CellPos,
+	pub max: CellPos
+}
+
+impl RouteBounds {
+	pub fn new(min: CellPos, max: CellPos) -> Self {
+		Self { min, max }
+	}
+
+	pub fn contains(&self, cell: CellPos) -> bool {
+		cell.0 >= self.min.0 && cell.1 >= self.min.1 &&
+		cell.0 <= self.max.0 && cell.1 <= self.max.1
+	}
+
+	fn step(&self, cell: CellPos, side: Side) -> Option<CellPos> {
+		let next = match side {
+			Side::Top => (cell.0, cell.1.checked_sub(1)?),
+			Side::Left => (cell.0.checked_sub(1)?, cell.1),
+			Side::Right => (cell.0.checked_add(1)?, cell.1),
+			Side::Bottom => (cell.0, cell.1.checked_add(1)?)
+		};
+
+		Some(next).filter(|c| self.contains(*c))
+	}
+}
+
+
+/// A* search for the cheapest Manhattan path from any of the `starts` to the `goal`.
+///
+/// Every step costs 1 and every bend costs `BEND_COST`. The returned path contains both the start and the goal.
+/// `is_passable` is never called for the starting cells.
+pub fn find_path<F: Fn(CellPos) -> bool>(starts: &[CellPos], goal: CellPos, bounds: RouteBounds, is_passable: F) -> Option<Vec<CellPos>> {
+	let heuristic = |cell: CellPos| distance(cell, goal);
+
+	let start_cells: HashSet<CellPos> = starts.iter().copied().collect();
+
+	let mut visited: Visited = HashMap::new();
+	let mut queue = BinaryHeap::new();
+
+	for &start in starts {
+		// Direction index 4 is "no direction yet".
+		visited.insert((start, 4), (0, None));
+		queue.push(Reverse((heuristic(start), 0, start, 4)));
+	}
+
+	if !start_cells.contains(&goal) && !is_passable(goal) {
+		return None;
+	}
+
+	while let Some(Reverse((_, cost, cell, dir))) = queue.pop() {
+		if cell == goal {
+			return Some(build_path(&visited, (cell, dir)));
+		}
+
+		// Already found a cheaper way here.
+		if visited.get(&(cell, dir)).is_some_and(|v| v.0 < cost) {
+			continue;
+		}
+
+		for (next_dir, &side) in DIRECTIONS.iter().enumerate() {
+			let next = match bounds.step(cell, side) {
+				Some(v) => v,
+				None => continue
+			};
+
+			if !start_cells.contains(&next) && !is_passable(next) {
+				continue;
+			}
+
+			let next_cost = cost + 1 + if dir != 4 && dir != next_dir { BEND_COST } else { 0 };
+
+			let is_cheaper = visited.get(&(next, next_dir)).is_none_or(|v| next_cost < v.0);
+
+			if is_cheaper {
+				visited.insert((next, next_dir), (next_cost, Some((cell, dir))));
+				queue.push(Reverse((next_cost + heuristic(next), next_cost, next, next_dir)));
+			}
+		}
+	}
+
+	None
+}
+
+/// Manhattan distance
+pub fn distance(from: CellPos, to: CellPos) -> usize {
+	(from.0.abs_diff(to.0) + from.1.abs_diff(to.1)) as usize
+}
+
+fn build_path(visited: &Visited, mut current: (CellPos, usize)) -> Vec<CellPos> {
+	let mut path = vec![current.0];
+
+	while let Some(&(_, Some(previous))) = visited.get(&current) {
+		path.push(previous.0);
+		current = previous;
+	}
+
+	path.reverse();
+
+	path
+}
@@ -8,18 +8,11 @@ pub type DimSizeType = usize;
 pub type DimSizeTuple = (DimSizeType, DimSizeType);
 
 
-pub fn modify_usize_with_isize(value: usize, modifier: isize) -> Option<usize> {
-	Some(if modifier.is_positive() {
-		value + modifier as usize
-	} else {
-		value.checked_sub(modifier.abs() as usize)?
-	})
-}
-
-pub fn offset_cell_pos(cell_pos: CellPos, offset: (isize, isize)) -> Option<CellPos> {
+/// `None` if the Cell Position would overflow.
+pub fn offset_cell_pos(cell_pos: CellPos, offset: CellPos) -> Option<CellPos> {
 	Some((
-		modify_usize_with_isize(cell_pos.0, offset.0)?,
-		modify_usize_with_isize(cell_pos.1, offset.1)?
+		cell_pos.0.checked_add(offset.0)?,
+		cell_pos.1.checked_add(offset.1)?
 	))
 }
 
@@ -179,38 +172,41 @@ pub enum NodeObjectSide {
 
 impl NodeObjectSide {
 	/// Returns the Cell Position based off of the Object Position and Dimensions.
-	pub fn get_cell_pos(self, obj_dim: &Dimensions, obj_pos: CellPos) -> Option<CellPos> {
+	pub fn get_cell_pos(self, obj_dim: &Dimensions, obj_pos: CellPos) -> CellPos {
 		let (pos_x, pos_y) = obj_pos;
 
-		Some(match self {
+		let width = obj_dim.width() as i32;
+		let height = obj_dim.height() as i32;
+
+		match self {
 			Self::Left(p) => {
 				(
-					pos_x.checked_sub(1)?,
-					pos_y + obj_dim.height() - p - 1
+					pos_x - 1,
+					pos_y + height - p as i32 - 1
 				)
 			}
 
 			Self::Right(p) => {
 				(
-					pos_x + obj_dim.width(),
-					pos_y + p
+					pos_x + width,
+					pos_y + p as i32
 				)
 			}
 
 			Self::Top(p) => {
 				(
-					pos_x + p,
-					pos_y.checked_sub(1)?
+					pos_x + p as i32,
+					pos_y - 1
 				)
 			}
 
 			Self::Bottom(p) => {
 				(
-					pos_x + obj_dim.width() - p - 1,
-					pos_y + obj_dim.height()
+					pos_x + width - p as i32 - 1,
+					pos_y + height
 				)
 			}
-		})
+		}
 	}
 
 	pub fn get_surrounding_cells(self, cell_pos: CellPos) -> [Option<CellPos>; 3] {
@@ -246,6 +242,29 @@ impl NodeObjectSide {
 		}
 	}
 
+	/// Return new `NodeObjectSide` rotated counter-clockwise.
+	pub fn next_side_counter_clockwise(self) -> Self {
+		match self {
+			Self::Left(i) => Self::Bottom(i),
+			Self::Right(i) => Self::Top(i),
+			Self::Top(i) => Self::Left(i),
+			Self::Bottom(i) => Self::Right(i),
+		}
+	}
+
+	/// Return new `NodeObjectSide` flipped horizontally on an Object of `obj_dim`. Left and Right swap, the cell positions stay in place.
+	pub fn mirrored(self, obj_dim: &Dimensions) -> Self {
+		let max_x = obj_dim.width().saturating_sub(1);
+		let max_y = obj_dim.height().saturating_sub(1);
+
+		match self {
+			Self::Left(i) => Self::Right(max_y.saturating_sub(i)),
+			Self::Right(i) => Self::Left(max_y.saturating_sub(i)),
+			Self::Top(i) => Self::Top(max_x.saturating_sub(i)),
+			Self::Bottom(i) => Self::Bottom(max_x.saturating_sub(i)),
+		}
+	}
+
 	/// Return new `NodeObjectSide` on opposite side.
 	pub fn opposite_side(self) -> Self {
 		match self {
@@ -420,6 +439,36 @@ impl Dimensions {
 }
 
 
+/// Rotation and mirroring of an Object from how it was created.
+///
+/// The Object is mirrored horizontally first and then rotated clockwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Orientation {
+	/// Quarter turns clockwise. 0 to 3.
+	pub rotation: u8,
+	pub mirrored: bool
+}
+
+impl Orientation {
+	pub fn rotated_clockwise(self) -> Self {
+		Self { rotation: (self.rotation + 1) % 4, ..self }
+	}
+
+	pub fn rotated_counter_clockwise(self) -> Self {
+		Self { rotation: (self.rotation + 3) % 4, ..self }
+	}
+
+	/// Mirroring after a rotation is the same as mirroring first and rotating the other way.
+	pub fn flipped(self) -> Self {
+		Self { rotation: (4 - self.rotation) % 4, mirrored: !self.mirrored }
+	}
+
+	pub fn is_default(&self) -> bool {
+		*self == Self::default()
+	}
+}
+
+
 pub struct Rectangle<I> {
 	pub x: I,
 	pub y: I,
@@ -0,0 +1,191 @@
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::config::{CanvasStateJson, ConfigJson};
+use crate::engine::Engine;
+
+
+/// Steps to run against a saved Canvas.
+///
+/// ```json
+/// {
+///     "name": "Half Adder",
+///     "canvas": "canvases/half_adder.json",
+///     "steps": [
+///         { "set": "a", "value": 1 },
+///         { "tick": 2 },
+///         { "expect": "sum", "value": 1 }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestbenchJson {
+	pub name: Option<String>,
+
+	/// Path of the saved Canvas. Relative to the testbench file.
+	pub canvas: String,
+
+	pub steps: Vec<TestStepJson>
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TestStepJson {
+	/// Sets a Switch or Button.
+	Set {
+		set: String,
+		#[serde(serialize_with = "serialize_bit", deserialize_with = "deserialize_bit")]
+		value: bool
+	},
+
+	/// Runs the engine for a number of ticks.
+	Tick {
+		tick: usize
+	},
+
+	/// Checks the value an LED is receiving.
+	Expect {
+		expect: String,
+		#[serde(serialize_with = "serialize_bit", deserialize_with = "deserialize_bit")]
+		value: bool
+	}
+}
+
+
+fn serialize_bit<S: Serializer>(value: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_u8(u8::from(*value))
+}
+
+/// Accepts `0`, `1`, `false` and `true`.
+fn deserialize_bit<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Bit {
+		Bool(bool),
+		Number(u8)
+	}
+
+	match Bit::deserialize(deserializer)? {
+		Bit::Bool(v) => Ok(v),
+		Bit::Number(0) => Ok(false),
+		Bit::Number(1) => Ok(true),
+		Bit::Number(v) => Err(serde::de::Error::custom(format!("Expected 0 or 1, found {v}")))
+	}
+}
+
+
+#[derive(Debug, Clone)]
+pub struct TestFailure {
+	/// Index of the failing step.
+	pub step: usize,
+	pub tick: usize,
+	pub message: String,
+
+	/// Value of every net when the step failed.
+	pub nets: Vec<(String, bool)>
+}
+
+impl fmt::Display for TestFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let nets: Vec<String> = self.nets.iter()
+			.map(|(name, value)| format!("{name}={}", u8::from(*value)))
+			.collect();
+
+		write!(f, "Step {} (Tick {}): {}\n\tNets: {}", self.step + 1, self.tick, self.message, nets.join(" "))
+	}
+}
+
+
+#[derive(Debug, Clone)]
+pub struct TestReport {
+	pub name: String,
+	pub failures: Vec<TestFailure>
+}
+
+impl TestReport {
+	pub fn is_success(&self) -> bool {
+		self.failures.is_empty()
+	}
+}
+
+impl fmt::Display for TestReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_success() {
+			return write!(f, "{}: ok", self.name);
+		}
+
+		write!(f, "{}: {} failure(s)", self.name, self.failures.len())?;
+
+		for failure in &self.failures {
+			write!(f, "\n{failure}")?;
+		}
+
+		Ok(())
+	}
+}
+
+
+/// Runs every step of the testbench. Failed expectations don't stop the run.
+///
+/// Errors if the Canvas can't be simulated or a step names something which doesn't exist.
+pub fn run_testbench(testbench: &TestbenchJson, canvas: &CanvasStateJson) -> Result<TestReport, String> {
+	let mut engine = Engine::new(canvas)?;
+
+	let mut failures = Vec::new();
+
+	for (index, step) in testbench.steps.iter().enumerate() {
+		match step {
+			TestStepJson::Set { set, value } => engine.set_input(set, *value)?,
+
+			TestStepJson::Tick { tick } => {
+				for _ in 0..*tick {
+					engine.tick();
+				}
+			}
+
+			TestStepJson::Expect { expect, value } => {
+				let found = engine.output(expect)?;
+
+				if found != *value {
+					failures.push(TestFailure {
+						step: index,
+						tick: engine.ticks(),
+						message: format!("Expected `{expect}` to be {} but it was {}", u8::from(*value), u8::from(found)),
+						nets: engine.net_values()
+					});
+				}
+			}
+		}
+	}
+
+	Ok(TestReport {
+		name: testbench.name.clone().unwrap_or_else(|| testbench.canvas.clone()),
+		failures
+	})
+}
+
+
+/// Loads a testbench file and the Canvas it references, then runs it.
+pub fn run_file(path: &Path) -> Result<TestReport, String> {
+	let testbench: TestbenchJson = read_json(path)?;
+
+	let canvas_path = path.parent().unwrap_or_else(|| Path::new("")).join(&testbench.canvas);
+
+	run_testbench(&testbench, &load_canvas(&canvas_path)?)
+}
+
+/// Loads a saved Canvas. Either a whole save or only the Canvas.
+pub fn load_canvas(path: &Path) -> Result<CanvasStateJson, String> {
+	read_json::<ConfigJson>(path)
+		.map(ConfigJson::into_inner_json)
+		.or_else(|_| read_json(path))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+	let contents = std::fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {e}", path.display()))?;
+
+	serde_json::from_str(&contents).map_err(|e| format!("Unable to parse {}: {e}", path.display()))
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::CellPos;
+use crate::config::{CanvasStateJson, NodeJson};
+use crate::netlist::{Netlist, NodeRef};
+use crate::object::ObjectType;
+use crate::route::{self, RouteBounds};
+
+
+/// Object on the critical path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathStep {
+	/// Object index in the Canvas.
+	pub object: usize,
+	/// Time in nanoseconds until the output of the Object is stable.
+	pub arrival: f64
+}
+
+
+/// Static timing of a Canvas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingReport {
+	/// From the input to the output.
+	pub steps: Vec<PathStep>,
+	/// Wire cells the path goes through.
+	pub wires: Vec<CellPos>,
+	/// Total delay in nanoseconds.
+	pub delay: f64
+}
+
+impl TimingReport {
+	/// Highest clock frequency in MHz which still lets the critical path settle. `None` if the path has no delay.
+	pub fn max_frequency(&self) -> Option<f64> {
+		if self.delay > 0.0 {
+			Some(1000.0 / self.delay)
+		} else {
+			None
+		}
+	}
+}
+
+
+/// (Arrival, Previous Object and the net it drives into this one from)
+type Arrival = (f64, Option<(usize, usize)>);
+
+#[derive(Clone, Copy)]
+enum Visit {
+	Visiting,
+	Done(Option<Arrival>)
+}
+
+
+/// Finds the longest combinational path of the Canvas using each Object's `propagation_delay`.
+///
+/// Paths start at Switches, Buttons and Clocks and end at LEDs. `None` if no input reaches an LED.
+pub fn critical_path(canvas: &CanvasStateJson) -> Result<Option<TimingReport>, String> {
+	let netlist = Netlist::new(canvas);
+
+	let mut analysis = Analysis {
+		canvas,
+		netlist: &netlist,
+		drivers: HashMap::new(),
+		visits: vec![None; canvas.objects.len()]
+	};
+
+	for net in 0..netlist.nets.len() {
+		let outputs: Vec<NodeRef> = netlist.nets[net].nodes.iter()
+			.copied()
+			.filter(|&n| analysis.node(n).is_output())
+			.collect();
+
+		analysis.drivers.insert(net, outputs);
+	}
+
+	let mut end: Option<(usize, f64)> = None;
+
+	for (index, object) in canvas.objects.iter().enumerate() {
+		if object.type_of() == ObjectType::Led {
+			if let Some((arrival, _)) = analysis.arrival(index)? {
+				if end.is_none_or(|(_, v)| arrival > v) {
+					end = Some((index, arrival));
+				}
+			}
+		}
+	}
+
+	let (end, delay) = match end {
+		Some(v) => v,
+		None => return Ok(None)
+	};
+
+	let mut steps = Vec::new();
+	let mut wires = Vec::new();
+
+	let mut current = Some(end);
+
+	while let Some(object) = current {
+		let (arrival, previous) = analysis.finished(object);
+
+		steps.push(PathStep { object, arrival });
+
+		current = previous.map(|(previous, net)| {
+			wires.append(&mut analysis.wire_path(previous, object, net));
+			previous
+		});
+	}
+
+	steps.reverse();
+
+	Ok(Some(TimingReport { steps, wires, delay }))
+}
+
+
+struct Analysis<'a> {
+	canvas: &'a CanvasStateJson,
+	netlist: &'a Netlist,
+
+	/// Output nodes of each net.
+	drivers: HashMap<usize, Vec<NodeRef>>,
+	visits: Vec<Option<Visit>>
+}
+
+impl<'a> Analysis<'a> {
+	fn node(&self, (object, index): NodeRef) -> &'a NodeJson {
+		&self.canvas.objects[object].nodes()[index]
+	}
+
+	fn finished(&self, object: usize) -> Arrival {
+		match self.visits[object] {
+			Some(Visit::Done(Some(v))) => v,
+			_ => unreachable!("Object on the path wasn't visited")
+		}
+	}
+
+	/// Time until the output of the Object is stable. `None` if no input drives it.
+	fn arrival(&mut self, object: usize) -> Result<Option<Arrival>, String> {
+		match self.visits[object] {
+			Some(Visit::Done(v)) => return Ok(v),
+
+			Some(Visit::Visiting) => {
+				let json = &self.canvas.objects[object];
+				let (x, y) = json.pos();
+
+				return Err(format!("Combinational loop through the {:?} at ({x}, {y})", json.type_of()));
+			}
+
+			None => ()
+		}
+
+		let type_of = self.canvas.objects[object].type_of();
+
+		let result = if matches!(type_of, ObjectType::Switch | ObjectType::Button | ObjectType::Clock) {
+			Some((type_of.propagation_delay(), None))
+		} else {
+			self.visits[object] = Some(Visit::Visiting);
+
+			let mut latest: Option<Arrival> = None;
+
+			let inputs: Vec<usize> = self.canvas.objects[object].nodes()
+				.iter()
+				.enumerate()
+				.filter(|(_, n)| n.is_input())
+				.filter_map(|(i, _)| self.netlist.net_of_node((object, i)))
+				.collect();
+
+			for net in inputs {
+				let drivers = self.drivers.get(&net).cloned().unwrap_or_default();
+
+				for (driver, _) in drivers {
+					if let Some((arrival, _)) = self.arrival(driver)? {
+						if latest.is_none_or(|(v, _)| arrival > v) {
+							latest = Some((arrival, Some((driver, net))));
+						}
+					}
+				}
+			}
+
+			latest.map(|(arrival, previous)| (arrival + type_of.propagation_delay(), previous))
+		};
+
+		self.visits[object] = Some(Visit::Done(result));
+
+		Ok(result)
+	}
+
+	/// Shortest run of wires in `net` from an output of `from` to an input of `to`.
+	fn wire_path(&self, from: usize, to: usize, net: usize) -> Vec<CellPos> {
+		let wires: HashSet<CellPos> = self.netlist.nets[net].wires.iter().copied().collect();
+
+		if wires.is_empty() {
+			return Vec::new();
+		}
+
+		let touching = |object: usize, outputs: bool| -> Vec<CellPos> {
+			self.netlist.nets[net].nodes.iter()
+				.filter(|&&(o, _)| o == object)
+				.map(|&n| self.node(n))
+				.filter(|n| n.is_output() == outputs)
+				.flat_map(|n| n.side.get_surrounding_cells(n.pos))
+				.flatten()
+				.filter(|c| wires.contains(c))
+				.collect()
+		};
+
+		let starts = touching(from, true);
+
+		let bounds = RouteBounds::new(
+			(wires.iter().map(|c| c.0).min().unwrap_or_default(), wires.iter().map(|c| c.1).min().unwrap_or_default()),
+			(wires.iter().map(|c| c.0).max().unwrap_or_default(), wires.iter().map(|c| c.1).max().unwrap_or_default())
+		);
+
+		touching(to, false)
+			.into_iter()
+			.filter_map(|goal| route::find_path(&starts, goal, bounds, |c| wires.contains(&c)))
+			.min_by_key(Vec::len)
+			.unwrap_or_default()
+	}
+}
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+
+use crate::NodeDirection;
+use crate::config::{CanvasStateJson, NodeJson, ObjectsJson};
+use crate::netlist::Netlist;
+use crate::object::ObjectType;
+
+use super::{Names, identifier};
+
+
+/// Value of an input which nothing drives.
+const UNDRIVEN: &str = "1'b0";
+
+
+struct Port {
+	name: String,
+	direction: NodeDirection,
+	/// Node inside of the module the port is connected to.
+	node: NodeJson
+}
+
+
+/// Exports the canvas as a structural Verilog module.
+///
+/// Switches, Buttons and Clocks become inputs and LEDs become outputs. Ports are named from node labels.
+/// Custom Objects are exported as sub-modules which follow the top module.
+pub fn export_module(name: &str, canvas: &CanvasStateJson) -> String {
+	let mut exporter = Exporter::default();
+
+	exporter.write_module(name, canvas, &[]);
+
+	// Top module first.
+	exporter.modules.into_iter().rev().collect::<Vec<_>>().join("\n\n") + "\n"
+}
+
+
+#[derive(Default)]
+struct Exporter {
+	module_names: Names,
+	modules: Vec<String>
+}
+
+impl Exporter {
+	/// Returns the name the module was written with.
+	fn write_module(&mut self, name: &str, canvas: &CanvasStateJson, ports: &[Port]) -> String {
+		let name = self.module_names.unique(name);
+
+		let port_nodes: Vec<NodeJson> = ports.iter().map(|p| p.node.clone()).collect();
+		let netlist = Netlist::new_with_ports(canvas, &port_nodes);
+
+		let mut module = ModuleWriter::new(canvas, &netlist, ports);
+
+		for (index, object) in canvas.objects.iter().enumerate() {
+			match object {
+				ObjectsJson::Normal(_) => module.write_object(index),
+
+				ObjectsJson::Custom(custom) => {
+					let ports = sub_module_ports(&custom.nodes, Some(&custom.inner_nodes));
+					let sub_name = self.write_module(custom.object_name.as_deref().unwrap_or("custom"), &custom.canvas, &ports);
+
+					module.write_instance(index, &sub_name, &ports);
+				}
+
+				ObjectsJson::Reference(reference) => {
+					let ports = sub_module_ports(&reference.nodes, None);
+					let sub_name = identifier(&format!("canvas_{}", reference.canvas_id));
+
+					module.body.push(format!("// `{sub_name}` is a separately saved Custom Object."));
+					module.write_instance(index, &sub_name, &ports);
+				}
+			}
+		}
+
+		self.modules.push(module.finish(&name));
+
+		name
+	}
+}
+
+
+fn sub_module_ports(nodes: &[NodeJson], inner_nodes: Option<&[NodeJson]>) -> Vec<Port> {
+	let mut names = Names::default();
+
+	nodes.iter()
+		.enumerate()
+		.map(|(i, node)| {
+			let inner = inner_nodes
+				.and_then(|v| v.get(i).cloned())
+				// Not connected to anything inside.
+				.unwrap_or_else(|| NodeJson { is_disabled: true, ..node.clone() });
+
+			Port {
+				name: names.unique(&node.label.clone().unwrap_or_else(|| format!("p{i}"))),
+				direction: node.direction,
+				node: inner
+			}
+		})
+		.collect()
+}
+
+
+struct ModuleWriter<'a> {
+	canvas: &'a CanvasStateJson,
+	netlist: &'a Netlist,
+
+	names: Names,
+	net_names: HashMap<usize, String>,
+	/// Is the net driven by an output or an input port.
+	driven: Vec<bool>,
+
+	/// (Name, Is Input)
+	ports: Vec<(String, bool)>,
+	wires: Vec<String>,
+	body: Vec<String>
+}
+
+impl<'a> ModuleWriter<'a> {
+	fn new(canvas: &'a CanvasStateJson, netlist: &'a Netlist, ports: &[Port]) -> Self {
+		let port_object = canvas.objects.len();
+
+		let driven = netlist.nets.iter()
+			.map(|net| net.nodes.iter().any(|&(object, node)| {
+				if object == port_object {
+					ports[node].direction.is_input()
+				} else {
+					canvas.objects[object].nodes()[node].is_output()
+				}
+			}))
+			.collect();
+
+		let mut this = Self {
+			canvas,
+			netlist,
+
+			names: Names::default(),
+			net_names: HashMap::new(),
+			driven,
+
+			ports: Vec::new(),
+			wires: Vec::new(),
+			body: Vec::new()
+		};
+
+		for (i, port) in ports.iter().enumerate() {
+			let name = this.declare_port(&port.name, port.direction.is_input());
+			this.connect_port(&name, port.direction.is_input(), netlist.net_of_node((port_object, i)));
+		}
+
+		// Inputs before Outputs so an input wired straight into an output keeps the input name.
+		for (index, object) in canvas.objects.iter().enumerate() {
+			if let ObjectsJson::Normal(json) = object {
+				if matches!(json.type_of, ObjectType::Switch | ObjectType::Button | ObjectType::Clock) {
+					let default = if json.type_of == ObjectType::Clock { "clk" } else { "in" };
+
+					let name = this.declare_port(&label_or(&json.nodes, default), true);
+
+					for net in this.object_nets(index, true) {
+						this.connect_port(&name, true, net);
+					}
+				}
+			}
+		}
+
+		for (index, object) in canvas.objects.iter().enumerate() {
+			if let ObjectsJson::Normal(json) = object {
+				if json.type_of == ObjectType::Led {
+					let name = this.declare_port(&label_or(&json.nodes, "out"), false);
+					this.connect_port(&name, false, this.object_nets(index, false).into_iter().next().flatten());
+				}
+			}
+		}
+
+		this
+	}
+
+	/// Nets of the inputs or outputs of an Object.
+	fn object_nets(&self, object: usize, outputs: bool) -> Vec<Option<usize>> {
+		self.canvas.objects[object].nodes()
+			.iter()
+			.enumerate()
+			.filter(|(_, n)| n.is_output() == outputs)
+			.map(|(i, _)| self.netlist.net_of_node((object, i)))
+			.collect()
+	}
+
+	/// Returns the unique name of the port.
+	fn declare_port(&mut self, name: &str, is_input: bool) -> String {
+		let name = self.names.unique(name);
+
+		self.ports.push((name.clone(), is_input));
+
+		name
+	}
+
+	/// Connects a port to a net. The net takes the name of the port unless it already has one.
+	fn connect_port(&mut self, name: &str, is_input: bool, net: Option<usize>) {
+		let net = match net {
+			Some(v) => v,
+
+			None => {
+				if !is_input {
+					self.body.push(format!("buf ({name}, {UNDRIVEN});"));
+				}
+
+				return;
+			}
+		};
+
+		match self.net_names.get(&net).cloned() {
+			None => {
+				self.net_names.insert(net, name.to_string());
+			}
+
+			Some(existing) if existing != name => {
+				if is_input && self.is_input_port(&existing) {
+					// Neither can drive the other.
+					self.body.push(format!("// `{name}` is left unconnected. It shares a net with the input `{existing}`."));
+				} else if is_input {
+					self.body.push(format!("buf ({existing}, {name});"));
+				} else {
+					let value = self.net_ref(Some(net));
+					self.body.push(format!("buf ({name}, {value});"));
+				}
+			}
+
+			_ => ()
+		}
+	}
+
+	fn is_input_port(&self, name: &str) -> bool {
+		self.ports.iter().any(|(n, is_input)| *is_input && n == name)
+	}
+
+	/// Name of a net which is read from.
+	fn net_ref(&mut self, net: Option<usize>) -> String {
+		match net {
+			Some(net) if self.driven[net] => self.net_name(net),
+			_ => String::from(UNDRIVEN)
+		}
+	}
+
+	/// Name of a net which is written to.
+	fn net_out(&mut self, net: Option<usize>) -> String {
+		match net {
+			Some(net) => self.net_name(net),
+
+			None => {
+				let name = self.names.unique("nc");
+				self.wires.push(name.clone());
+				name
+			}
+		}
+	}
+
+	fn net_name(&mut self, net: usize) -> String {
+		if let Some(name) = self.net_names.get(&net) {
+			return name.clone();
+		}
+
+		let name = self.names.unique(&format!("n{net}"));
+
+		self.net_names.insert(net, name.clone());
+		self.wires.push(name.clone());
+
+		name
+	}
+
+
+	fn write_object(&mut self, index: usize) {
+		let type_of = self.canvas.objects[index].type_of();
+
+		let inputs = self.object_nets(index, false);
+		let outputs = self.object_nets(index, true);
+
+		let primitive = match type_of {
+			ObjectType::AndGate => "and",
+			ObjectType::OrGate => "or",
+			ObjectType::XorGate => "xor",
+			ObjectType::NotGate => "not",
+			ObjectType::TestObj => "buf",
+
+			// LEDs pass their value through to their output.
+			ObjectType::Led => {
+				let output = outputs.first().copied().flatten();
+
+				let is_connected = output.is_some_and(|net| {
+					let net = &self.netlist.nets[net];
+					net.nodes.len() > 1 || !net.wires.is_empty()
+				});
+
+				if is_connected {
+					let value = self.net_ref(inputs.first().copied().flatten());
+					let output = self.net_out(output);
+
+					self.body.push(format!("buf l{index} ({output}, {value});"));
+				}
+
+				return;
+			}
+
+			// Ports
+			ObjectType::Switch |
+			ObjectType::Button |
+			ObjectType::Clock => return
+		};
+
+		for output in outputs {
+			let mut terminals = vec![self.net_out(output)];
+
+			for &input in &inputs {
+				terminals.push(self.net_ref(input));
+			}
+
+			self.body.push(format!("{primitive} g{index} ({});", terminals.join(", ")));
+		}
+	}
+
+	fn write_instance(&mut self, index: usize, module: &str, ports: &[Port]) {
+		let connections: Vec<String> = ports.iter()
+			.enumerate()
+			.map(|(i, port)| {
+				let net = self.netlist.net_of_node((index, i));
+
+				let value = if port.direction.is_input() {
+					self.net_ref(net)
+				} else {
+					self.net_out(net)
+				};
+
+				format!(".{}({value})", port.name)
+			})
+			.collect();
+
+		self.body.push(format!("{module} u{index} ({});", connections.join(", ")));
+	}
+
+
+	fn finish(self, name: &str) -> String {
+		let mut lines = Vec::new();
+
+		if self.ports.is_empty() {
+			lines.push(format!("module {name};"));
+		} else {
+			let port_names: Vec<&str> = self.ports.iter().map(|(n, _)| n.as_str()).collect();
+			lines.push(format!("module {name} ({});", port_names.join(", ")));
+		}
+
+		for (port, is_input) in &self.ports {
+			lines.push(format!("\t{} {port};", if *is_input { "input" } else { "output" }));
+		}
+
+		if !self.wires.is_empty() {
+			lines.push(String::new());
+
+			for wire in &self.wires {
+				lines.push(format!("\twire {wire};"));
+			}
+		}
+
+		if !self.body.is_empty() {
+			lines.push(String::new());
+
+			for line in &self.body {
+				lines.push(format!("\t{line}"));
+			}
+		}
+
+		lines.push(String::from("endmodule"));
+
+		lines.join("\n")
+	}
+}
+
+
+/// First label of the nodes.
+fn label_or(nodes: &[NodeJson], default: &str) -> String {
+	nodes.iter()
+		.find_map(|n| n.label.clone())
+		.unwrap_or_else(|| default.to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn node(pos: (i32, i32), side: &str, direction: &str, label: Option<&str>) -> serde_json::Value {
+		serde_json::json!({ "pos": pos, "side": { side: 0 }, "direction": direction, "accepts": "Gpio", "label": label, "is_disabled": false })
+	}
+
+	fn object(type_of: &str, id: usize, pos: (i32, i32), nodes: Vec<serde_json::Value>) -> serde_json::Value {
+		serde_json::json!({ "Normal": { "type_of": type_of, "id": id, "pos": pos, "dim": [1, 1], "nodes": nodes } })
+	}
+
+	#[test]
+	fn switches_on_one_net_dont_drive_each_other() {
+		let canvas: CanvasStateJson = serde_json::from_value(serde_json::json!({
+			"objects": [
+				object("Switch", 1, (8, 8), vec![node((9, 8), "Right", "Output", Some("a"))]),
+				object("Switch", 2, (8, 10), vec![node((9, 10), "Right", "Output", Some("b"))]),
+				object("Led", 3, (12, 9), vec![node((11, 9), "Left", "Input", Some("y"))])
+			],
+			"color_palette": [[0, 0, 0], [0, 0, 0]],
+			"pixels": { "0": [[10, 8], [10, 9], [10, 10]] },
+			"text_objects": []
+		})).unwrap();
+
+		let verilog = export_module("top", &canvas);
+
+		assert!(!verilog.contains("buf (a, b);"), "{}", verilog);
+		assert!(verilog.contains("`b` is left unconnected. It shares a net with the input `a`."), "{}", verilog);
+		assert!(verilog.contains("buf (y, a);"), "{}", verilog);
+	}
+}
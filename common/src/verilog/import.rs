@@ -0,0 +1,853 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use crate::circuit::{Circuit, Signal};
+use crate::config::CanvasStateJson;
+use crate::expr::{self, Expr, ParseError};
+use crate::layout;
+use crate::object::ObjectType;
+
+
+/// Deepest module instance nesting which is flattened.
+const MAX_DEPTH: usize = 32;
+
+
+/// Parses the Verilog and lays out its top module.
+pub fn import_canvas(source: &str) -> Result<CanvasStateJson, String> {
+	let circuit = import_circuit(source)?;
+
+	layout::layout_circuit(&circuit)
+}
+
+/// Parses a structural Verilog subset into a gate network.
+///
+/// Supports `module`, `input`, `output`, `wire`, the `and` `or` `xor` `not` `nand` `nor` `xnor` `buf` primitives,
+/// `assign` with `~ & ^ |` and instances of other modules in the same source. The top module is the one no other module instantiates.
+pub fn import_circuit(source: &str) -> Result<Circuit, String> {
+	let modules = Parser::new(source).parse_modules().map_err(|e| e.to_string())?;
+
+	let top = modules.iter()
+		.find(|m| !modules.iter().any(|o| o.items.iter().any(|i| matches!(i, Item::Instance { module, .. } if *module == m.name))))
+		.ok_or_else(|| String::from("Unable to find the top module. Modules instantiate each other"))?;
+
+	let mut drivers = Drivers::default();
+
+	drivers.flatten(&modules, top, "", &HashMap::new(), 0)?;
+
+	drivers.into_circuit(top)
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+	Input,
+	Output,
+	Wire
+}
+
+
+#[derive(Debug)]
+enum Item {
+	/// (Primitive, Terminals). Outputs come first.
+	Gate(String, Vec<Term>),
+
+	Assign(String, Expr),
+
+	Instance {
+		module: String,
+		name: String,
+		connections: Connections
+	}
+}
+
+#[derive(Debug)]
+enum Connections {
+	Named(Vec<(String, Option<Term>)>),
+	Ordered(Vec<Term>)
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+	Net(String),
+	Const(bool)
+}
+
+
+#[derive(Debug)]
+struct Module {
+	name: String,
+
+	/// Port order.
+	ports: Vec<String>,
+	directions: HashMap<String, Direction>,
+
+	items: Vec<Item>
+}
+
+impl Module {
+	fn inputs(&self) -> impl Iterator<Item = &String> {
+		self.ports.iter().filter(move |p| self.directions.get(*p) == Some(&Direction::Input))
+	}
+
+	fn outputs(&self) -> impl Iterator<Item = &String> {
+		self.ports.iter().filter(move |p| self.directions.get(*p) == Some(&Direction::Output))
+	}
+}
+
+
+
+// Parsing
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Ident(String),
+	Number(String),
+	Symbol(char)
+}
+
+
+struct Parser {
+	/// (Line, Column, Token)
+	tokens: Vec<(usize, usize, Token)>,
+	index: usize
+}
+
+impl Parser {
+	fn new(source: &str) -> Self {
+		let mut tokens = Vec::new();
+
+		for (line_index, line) in strip_block_comments(source).lines().enumerate() {
+			let line_number = line_index + 1;
+
+			let line = line.find("//").map_or(line, |i| &line[..i]);
+
+			let mut chars = line.char_indices().peekable();
+
+			while let Some((i, c)) = chars.next() {
+				let column = i + 1;
+
+				let token = if c.is_ascii_alphabetic() || c == '_' || c == '\\' {
+					let mut name = if c == '\\' { String::new() } else { c.to_string() };
+
+					// Escaped identifiers end at whitespace.
+					while let Some(&(_, next)) = chars.peek() {
+						let is_part = if c == '\\' {
+							!next.is_whitespace()
+						} else {
+							next.is_ascii_alphanumeric() || next == '_' || next == '$'
+						};
+
+						if !is_part {
+							break;
+						}
+
+						name.push(next);
+						chars.next();
+					}
+
+					Token::Ident(name)
+				} else if c.is_ascii_digit() || c == '\'' {
+					let mut number = c.to_string();
+
+					while let Some(&(_, c)) = chars.peek() {
+						if c.is_ascii_alphanumeric() || c == '\'' || c == '_' {
+							number.push(c);
+							chars.next();
+						} else {
+							break;
+						}
+					}
+
+					Token::Number(number)
+				} else if c.is_whitespace() {
+					continue;
+				} else {
+					Token::Symbol(c)
+				};
+
+				tokens.push((line_number, column, token));
+			}
+		}
+
+		Self { tokens, index: 0 }
+	}
+
+	fn error<S: Into<String>>(&self, message: S) -> ParseError {
+		let (line, column) = self.tokens.get(self.index)
+			.or_else(|| self.tokens.last())
+			.map_or((1, 1), |v| (v.0, v.1));
+
+		ParseError::new(line, column, message)
+	}
+
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.index).map(|v| &v.2)
+	}
+
+	fn next(&mut self) -> Option<Token> {
+		let token = self.tokens.get(self.index).map(|v| v.2.clone());
+		self.index += 1;
+		token
+	}
+
+	fn is_symbol(&self, symbol: char) -> bool {
+		self.peek() == Some(&Token::Symbol(symbol))
+	}
+
+	fn expect_symbol(&mut self, symbol: char) -> Result<(), ParseError> {
+		if self.is_symbol(symbol) {
+			self.index += 1;
+			Ok(())
+		} else {
+			Err(self.error(format!("Expected `{symbol}`")))
+		}
+	}
+
+	fn expect_ident(&mut self) -> Result<String, ParseError> {
+		match self.peek() {
+			Some(Token::Ident(v)) => {
+				let v = v.clone();
+				self.index += 1;
+				Ok(v)
+			}
+
+			_ => Err(self.error("Expected a name"))
+		}
+	}
+
+	fn expect_term(&mut self) -> Result<Term, ParseError> {
+		match self.peek() {
+			Some(Token::Number(v)) => {
+				let value = parse_constant(v).ok_or_else(|| self.error(format!("Only single bit constants are supported, found `{v}`")))?;
+				self.index += 1;
+				Ok(Term::Const(value))
+			}
+
+			Some(Token::Symbol('[')) => Err(self.error("Vectors aren't supported")),
+
+			_ => self.expect_ident().map(Term::Net)
+		}
+	}
+
+
+	fn parse_modules(mut self) -> Result<Vec<Module>, ParseError> {
+		let mut modules: Vec<Module> = Vec::new();
+
+		while let Some(token) = self.next() {
+			match token {
+				Token::Ident(v) if v == "module" => {
+					let module = self.parse_module()?;
+
+					if modules.iter().any(|m| m.name == module.name) {
+						return Err(self.error(format!("Module `{}` is defined twice", module.name)));
+					}
+
+					modules.push(module);
+				}
+
+				_ => {
+					self.index -= 1;
+					return Err(self.error("Expected `module`"));
+				}
+			}
+		}
+
+		if modules.is_empty() {
+			return Err(self.error("No modules found"));
+		}
+
+		Ok(modules)
+	}
+
+	fn parse_module(&mut self) -> Result<Module, ParseError> {
+		let mut module = Module {
+			name: self.expect_ident()?,
+			ports: Vec::new(),
+			directions: HashMap::new(),
+			items: Vec::new()
+		};
+
+		if self.is_symbol('#') {
+			return Err(self.error("Parameters aren't supported"));
+		}
+
+		// Port List. Either only names or with directions.
+		if self.is_symbol('(') {
+			self.index += 1;
+
+			let mut direction = None;
+
+			while !self.is_symbol(')') {
+				let mut name = self.expect_ident()?;
+
+				if let Some(dir) = parse_direction(&name) {
+					direction = Some(dir);
+
+					name = self.expect_ident()?;
+
+					if name == "wire" {
+						name = self.expect_ident()?;
+					}
+				}
+
+				if self.is_symbol('[') {
+					return Err(self.error("Vectors aren't supported"));
+				}
+
+				if let Some(dir) = direction {
+					module.directions.insert(name.clone(), dir);
+				}
+
+				module.ports.push(name);
+
+				if !self.is_symbol(')') {
+					self.expect_symbol(',')?;
+				}
+			}
+
+			self.index += 1;
+		}
+
+		self.expect_symbol(';')?;
+
+		loop {
+			let keyword = self.expect_ident()?;
+
+			match keyword.as_str() {
+				"endmodule" => break,
+
+				"input" | "output" | "wire" => {
+					let direction = parse_direction(&keyword).unwrap();
+
+					if self.peek() == Some(&Token::Ident(String::from("wire"))) {
+						self.index += 1;
+					}
+
+					loop {
+						if self.is_symbol('[') {
+							return Err(self.error("Vectors aren't supported"));
+						}
+
+						let name = self.expect_ident()?;
+
+						if direction != Direction::Wire {
+							if !module.ports.contains(&name) {
+								return Err(self.error(format!("`{name}` isn't in the port list")));
+							}
+
+							module.directions.insert(name, direction);
+						}
+
+						if self.is_symbol(',') {
+							self.index += 1;
+						} else {
+							break;
+						}
+					}
+
+					self.expect_symbol(';')?;
+				}
+
+				"assign" => {
+					let target = self.expect_ident()?;
+
+					self.expect_symbol('=')?;
+
+					let expr = self.parse_expression()?;
+
+					module.items.push(Item::Assign(target, expr));
+				}
+
+				"and" | "or" | "xor" | "not" | "nand" | "nor" | "xnor" | "buf" => {
+					if self.is_symbol('#') {
+						return Err(self.error("Delays aren't supported"));
+					}
+
+					// Multiple instances can be separated by commas.
+					loop {
+						if !self.is_symbol('(') {
+							self.expect_ident()?;
+						}
+
+						self.expect_symbol('(')?;
+
+						let mut terms = vec![self.expect_term()?];
+
+						while self.is_symbol(',') {
+							self.index += 1;
+							terms.push(self.expect_term()?);
+						}
+
+						self.expect_symbol(')')?;
+
+						let min_terms = if matches!(keyword.as_str(), "not" | "buf") { 2 } else { 3 };
+
+						if terms.len() < min_terms {
+							return Err(self.error(format!("`{keyword}` needs at least {min_terms} terminals")));
+						}
+
+						module.items.push(Item::Gate(keyword.clone(), terms));
+
+						if self.is_symbol(',') {
+							self.index += 1;
+						} else {
+							break;
+						}
+					}
+
+					self.expect_symbol(';')?;
+				}
+
+				"inout" | "reg" | "always" | "initial" | "parameter" | "localparam" => {
+					self.index -= 1;
+					return Err(self.error(format!("`{keyword}` isn't supported")));
+				}
+
+				_ => {
+					if self.is_symbol('#') {
+						return Err(self.error("Parameters aren't supported"));
+					}
+
+					let name = self.expect_ident()?;
+
+					self.expect_symbol('(')?;
+
+					let connections = if self.is_symbol('.') {
+						let mut list = Vec::new();
+
+						while self.is_symbol('.') {
+							self.index += 1;
+
+							let port = self.expect_ident()?;
+
+							self.expect_symbol('(')?;
+
+							let term = if self.is_symbol(')') { None } else { Some(self.expect_term()?) };
+
+							self.expect_symbol(')')?;
+
+							list.push((port, term));
+
+							if self.is_symbol(',') {
+								self.index += 1;
+							}
+						}
+
+						Connections::Named(list)
+					} else {
+						let mut list = Vec::new();
+
+						while !self.is_symbol(')') {
+							list.push(self.expect_term()?);
+
+							if !self.is_symbol(')') {
+								self.expect_symbol(',')?;
+							}
+						}
+
+						Connections::Ordered(list)
+					};
+
+					self.expect_symbol(')')?;
+					self.expect_symbol(';')?;
+
+					module.items.push(Item::Instance { module: keyword, name, connections });
+				}
+			}
+		}
+
+		if let Some(port) = module.ports.iter().find(|p| !module.directions.contains_key(*p)) {
+			return Err(self.error(format!("Port `{port}` of `{}` has no direction", module.name)));
+		}
+
+		Ok(module)
+	}
+
+	/// Parses until `;` with the Boolean expression parser.
+	fn parse_expression(&mut self) -> Result<Expr, ParseError> {
+		let (line, column) = self.tokens.get(self.index).map_or((1, 1), |v| (v.0, v.1));
+
+		let mut text = String::new();
+
+		loop {
+			match self.next() {
+				Some(Token::Symbol(';')) => break,
+				Some(Token::Symbol(c)) => text.push(c),
+				Some(Token::Number(_)) => {
+					self.index -= 1;
+
+					match self.expect_term()? {
+						Term::Const(v) => text.push(if v { '1' } else { '0' }),
+						Term::Net(v) => text.push_str(&v)
+					}
+				}
+
+				Some(Token::Ident(v)) => text.push_str(&v),
+				None => return Err(self.error("Expected `;`"))
+			}
+
+			text.push(' ');
+		}
+
+		expr::parse_expression(&text).map_err(|e| ParseError::new(line, column, e.message))
+	}
+}
+
+
+fn parse_direction(value: &str) -> Option<Direction> {
+	match value {
+		"input" => Some(Direction::Input),
+		"output" => Some(Direction::Output),
+		"wire" => Some(Direction::Wire),
+		_ => None
+	}
+}
+
+/// `0`, `1`, `1'b1`, `'h0` ..
+fn parse_constant(value: &str) -> Option<bool> {
+	let value = value.replace('_', "");
+
+	let (width, rest) = match value.find('\'') {
+		Some(i) => (&value[..i], &value[i + 1..]),
+		None => return value.parse::<u32>().ok().filter(|v| *v <= 1).map(|v| v == 1)
+	};
+
+	if !width.is_empty() && width != "1" {
+		return None;
+	}
+
+	let rest = rest.trim_start_matches(['s', 'S']);
+
+	let radix = match rest.chars().next()? {
+		'b' | 'B' => 2,
+		'o' | 'O' => 8,
+		'd' | 'D' => 10,
+		'h' | 'H' => 16,
+		_ => return None
+	};
+
+	u32::from_str_radix(&rest[1..], radix).ok().filter(|v| *v <= 1).map(|v| v == 1)
+}
+
+/// Replaces block comments with spaces so positions stay the same.
+fn strip_block_comments(source: &str) -> String {
+	let mut output = String::with_capacity(source.len());
+	let mut rest = source;
+
+	while let Some(start) = rest.find("/*") {
+		output.push_str(&rest[..start]);
+
+		let end = rest[start..].find("*/").map_or(rest.len(), |i| start + i + 2);
+
+		output.extend(rest[start..end].chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+
+		rest = &rest[end..];
+	}
+
+	output.push_str(rest);
+
+	output
+}
+
+
+
+// Flattening
+
+#[derive(Debug, Clone)]
+enum Driver {
+	/// (Object Type, Inverted, Inputs)
+	Gate(ObjectType, bool, Vec<Term>),
+	Buffer(Term),
+	Expr(Expr)
+}
+
+
+#[derive(Debug, Clone, Copy)]
+enum Value {
+	Const(bool),
+	Signal(Signal)
+}
+
+
+#[derive(Default)]
+struct Drivers {
+	/// Net -> What drives it.
+	nets: HashMap<String, Driver>,
+
+	circuit: Circuit,
+	values: HashMap<String, Value>,
+	visiting: Vec<String>
+}
+
+impl Drivers {
+	/// Adds the drivers of the module. Nets are prefixed with the instance path. Ports are replaced by what they're connected to.
+	fn flatten(&mut self, modules: &[Module], module: &Module, prefix: &str, ports: &HashMap<String, Term>, depth: usize) -> Result<(), String> {
+		if depth > MAX_DEPTH {
+			return Err(format!("Module `{}` is nested too deep", module.name));
+		}
+
+		let resolve = |name: &str| -> Term {
+			ports.get(name).cloned().unwrap_or_else(|| Term::Net(format!("{prefix}{name}")))
+		};
+
+		for item in &module.items {
+			match item {
+				Item::Gate(primitive, terms) => {
+					let (type_of, inverted) = match primitive.as_str() {
+						"and" => (ObjectType::AndGate, false),
+						"or" => (ObjectType::OrGate, false),
+						"xor" => (ObjectType::XorGate, false),
+						"nand" => (ObjectType::AndGate, true),
+						"nor" => (ObjectType::OrGate, true),
+						"xnor" => (ObjectType::XorGate, true),
+						_ => (ObjectType::NotGate, false)
+					};
+
+					// `not` and `buf` can have multiple outputs. The rest have one.
+					let output_count = if primitive == "not" || primitive == "buf" { terms.len() - 1 } else { 1 };
+
+					let inputs: Vec<Term> = terms[output_count..].iter().map(|t| term_resolve(t, &resolve)).collect();
+
+					for output in &terms[..output_count] {
+						let driver = if primitive == "buf" {
+							Driver::Buffer(inputs[0].clone())
+						} else {
+							Driver::Gate(type_of, inverted, inputs.clone())
+						};
+
+						self.add_driver(term_resolve(output, &resolve), driver)?;
+					}
+				}
+
+				Item::Assign(target, expr) => {
+					let expr = rename_expr(expr, &resolve);
+					self.add_driver(resolve(target), Driver::Expr(expr))?;
+				}
+
+				Item::Instance { module: module_name, name, connections } => {
+					let sub = modules.iter()
+						.find(|m| &m.name == module_name)
+						.ok_or_else(|| format!("Unknown module `{module_name}`"))?;
+
+					let sub_prefix = format!("{prefix}{name}.");
+
+					let mut sub_ports: HashMap<String, Term> = HashMap::new();
+
+					match connections {
+						Connections::Named(list) => {
+							for (port, term) in list {
+								if !sub.ports.contains(port) {
+									return Err(format!("Module `{module_name}` has no port `{port}`"));
+								}
+
+								if let Some(term) = term {
+									sub_ports.insert(port.clone(), term_resolve(term, &resolve));
+								}
+							}
+						}
+
+						Connections::Ordered(list) => {
+							if list.len() > sub.ports.len() {
+								return Err(format!("Instance `{name}` has more connections than `{module_name}` has ports"));
+							}
+
+							for (port, term) in sub.ports.iter().zip(list) {
+								sub_ports.insert(port.clone(), term_resolve(term, &resolve));
+							}
+						}
+					}
+
+					// Unconnected inputs are low.
+					for port in sub.inputs() {
+						sub_ports.entry(port.clone()).or_insert(Term::Const(false));
+					}
+
+					for port in sub.outputs() {
+						if let Some(Term::Const(_)) = sub_ports.get(port) {
+							return Err(format!("Output `{port}` of `{name}` is connected to a constant"));
+						}
+					}
+
+					self.flatten(modules, sub, &sub_prefix, &sub_ports, depth + 1)?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	fn add_driver(&mut self, net: Term, driver: Driver) -> Result<(), String> {
+		match net {
+			Term::Const(_) => Err(String::from("A constant can't be driven")),
+
+			Term::Net(net) => {
+				match self.nets.entry(net) {
+					Entry::Occupied(e) => Err(format!("`{}` has more than one driver", e.key())),
+
+					Entry::Vacant(e) => {
+						e.insert(driver);
+						Ok(())
+					}
+				}
+			}
+		}
+	}
+
+
+	fn into_circuit(mut self, top: &Module) -> Result<Circuit, String> {
+		for input in top.inputs() {
+			if self.nets.contains_key(input) {
+				return Err(format!("Input `{input}` is driven inside of the module"));
+			}
+
+			let signal = self.circuit.add_input(input);
+			self.values.insert(input.clone(), Value::Signal(signal));
+		}
+
+		for output in top.outputs() {
+			match self.value(output)? {
+				Value::Signal(signal) => self.circuit.add_output(output, signal),
+				Value::Const(v) => return Err(format!("Output `{output}` is always {}. Constant outputs aren't supported", v as u8))
+			}
+		}
+
+		Ok(self.circuit)
+	}
+
+	fn value(&mut self, net: &str) -> Result<Value, String> {
+		if let Some(value) = self.values.get(net) {
+			return Ok(*value);
+		}
+
+		if self.visiting.iter().any(|v| v == net) {
+			return Err(format!("`{net}` is part of a combinational loop"));
+		}
+
+		let driver = self.nets.get(net).cloned().ok_or_else(|| format!("Nothing drives `{net}`"))?;
+
+		self.visiting.push(net.to_string());
+
+		let value = match driver {
+			Driver::Buffer(term) => self.term_value(&term)?,
+
+			Driver::Gate(type_of, inverted, inputs) => {
+				let inputs = inputs.iter()
+					.map(|t| self.term_value(t))
+					.collect::<Result<Vec<_>, _>>()?;
+
+				let value = self.gate(type_of, inputs);
+
+				if inverted {
+					self.gate(ObjectType::NotGate, vec![value])
+				} else {
+					value
+				}
+			}
+
+			Driver::Expr(expr) => self.expr_value(&expr)?
+		};
+
+		self.visiting.pop();
+
+		self.values.insert(net.to_string(), value);
+
+		Ok(value)
+	}
+
+	fn term_value(&mut self, term: &Term) -> Result<Value, String> {
+		match term {
+			Term::Const(v) => Ok(Value::Const(*v)),
+			Term::Net(net) => self.value(net)
+		}
+	}
+
+	fn expr_value(&mut self, expr: &Expr) -> Result<Value, String> {
+		Ok(match expr {
+			Expr::Const(v) => Value::Const(*v),
+			Expr::Var(net) => self.value(net)?,
+
+			Expr::Not(v) => {
+				let v = self.expr_value(v)?;
+				self.gate(ObjectType::NotGate, vec![v])
+			}
+
+			Expr::And(l, r) | Expr::Or(l, r) | Expr::Xor(l, r) => {
+				let type_of = match expr {
+					Expr::And(..) => ObjectType::AndGate,
+					Expr::Or(..) => ObjectType::OrGate,
+					_ => ObjectType::XorGate
+				};
+
+				let inputs = vec![self.expr_value(l)?, self.expr_value(r)?];
+
+				self.gate(type_of, inputs)
+			}
+		})
+	}
+
+	/// Adds the gate as a chain of 2-input gates. Constant inputs are folded.
+	fn gate(&mut self, type_of: ObjectType, inputs: Vec<Value>) -> Value {
+		// Result when every input is a constant.
+		let mut constant = type_of == ObjectType::AndGate;
+
+		let mut signals = Vec::new();
+
+		for input in inputs {
+			match (type_of, input) {
+				(_, Value::Signal(s)) => signals.push(s),
+
+				(ObjectType::AndGate, Value::Const(false)) => return Value::Const(false),
+				(ObjectType::OrGate, Value::Const(true)) => return Value::Const(true),
+				(ObjectType::XorGate, Value::Const(v)) => constant ^= v,
+				(ObjectType::NotGate, Value::Const(v)) => return Value::Const(!v),
+
+				_ => ()
+			}
+		}
+
+		if type_of == ObjectType::NotGate {
+			return Value::Signal(self.circuit.add_gate(ObjectType::NotGate, signals));
+		}
+
+		let mut signals = signals.into_iter();
+
+		let first = match signals.next() {
+			Some(v) => v,
+			None => return Value::Const(constant)
+		};
+
+		let signal = signals.fold(first, |acc, s| self.circuit.add_gate(type_of, vec![acc, s]));
+
+		// XOR with a high constant.
+		if type_of == ObjectType::XorGate && constant {
+			Value::Signal(self.circuit.add_gate(ObjectType::NotGate, vec![signal]))
+		} else {
+			Value::Signal(signal)
+		}
+	}
+}
+
+
+fn term_resolve<F: Fn(&str) -> Term>(term: &Term, resolve: &F) -> Term {
+	match term {
+		Term::Net(name) => resolve(name),
+		Term::Const(v) => Term::Const(*v)
+	}
+}
+
+fn rename_expr<F: Fn(&str) -> Term>(expr: &Expr, resolve: &F) -> Expr {
+	match expr {
+		Expr::Const(v) => Expr::Const(*v),
+
+		Expr::Var(name) => match resolve(name) {
+			Term::Net(v) => Expr::Var(v),
+			Term::Const(v) => Expr::Const(v)
+		},
+
+		Expr::Not(v) => Expr::Not(Box::new(rename_expr(v, resolve))),
+		Expr::And(l, r) => Expr::And(Box::new(rename_expr(l, resolve)), Box::new(rename_expr(r, resolve))),
+		Expr::Or(l, r) => Expr::Or(Box::new(rename_expr(l, resolve)), Box::new(rename_expr(r, resolve))),
+		Expr::Xor(l, r) => Expr::Xor(Box::new(rename_expr(l, resolve)), Box::new(rename_expr(r, resolve)))
+	}
+}
@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+mod export;
+mod import;
+
+pub use export::export_module;
+pub use import::{import_canvas, import_circuit};
+
+
+const KEYWORDS: [&str; 24] = [
+	"always", "and", "assign", "begin", "buf", "case", "else", "end", "endmodule", "if", "initial", "inout",
+	"input", "module", "nand", "nor", "not", "or", "output", "reg", "supply0", "supply1", "wire", "xor"
+];
+
+
+/// Turns any text into a valid Verilog identifier.
+pub fn identifier(value: &str) -> String {
+	let mut ident: String = value.trim()
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+		.collect();
+
+	if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+		ident.insert(0, '_');
+	}
+
+	if KEYWORDS.contains(&ident.as_str()) {
+		ident.push('_');
+	}
+
+	ident
+}
+
+
+/// Identifiers already used inside a module.
+#[derive(Debug, Default)]
+struct Names(HashSet<String>);
+
+impl Names {
+	/// Returns `base` as an identifier. A number is appended if it's already used.
+	fn unique(&mut self, base: &str) -> String {
+		let base = identifier(base);
+
+		let mut name = base.clone();
+		let mut count = 1;
+
+		while !self.0.insert(name.clone()) {
+			name = format!("{base}_{count}");
+			count += 1;
+		}
+
+		name
+	}
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+use std::process::Command;
+
+use circuit_sim_common::{config::CanvasStateJson, testbench::{self, TestbenchJson, TestStepJson}};
+
+
+/// Runs every testbench in `testbenches/`.
+#[test]
+fn testbenches() {
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("testbenches");
+
+	let mut failures = Vec::new();
+
+	for entry in std::fs::read_dir(&dir).expect("Reading testbenches") {
+		let path = entry.expect("Reading testbench").path();
+
+		if path.extension().is_none_or(|v| v != "json") {
+			continue;
+		}
+
+		match testbench::run_file(&path) {
+			Ok(report) if report.is_success() => (),
+			Ok(report) => failures.push(report.to_string()),
+			Err(e) => failures.push(format!("{}: {e}", path.display()))
+		}
+	}
+
+	assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
+
+
+fn full_adder_canvas() -> CanvasStateJson {
+	let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testbenches/canvases/full_adder.json");
+
+	testbench::load_canvas(&path).expect("Loading full adder")
+}
+
+fn testbench_of(steps: Vec<TestStepJson>) -> TestbenchJson {
+	TestbenchJson {
+		name: Some(String::from("Wrong Full Adder")),
+		canvas: String::from("canvases/full_adder.json"),
+		steps
+	}
+}
+
+/// 1 + 0 + 0 is expected to have a sum of 0.
+fn wrong_steps() -> Vec<TestStepJson> {
+	vec![
+		TestStepJson::Set { set: String::from("a"), value: true },
+		TestStepJson::Set { set: String::from("b"), value: false },
+		TestStepJson::Set { set: String::from("cin"), value: false },
+		TestStepJson::Tick { tick: 5 },
+		TestStepJson::Expect { expect: String::from("sum"), value: false },
+		TestStepJson::Expect { expect: String::from("cout"), value: false }
+	]
+}
+
+
+#[test]
+fn failing_expect_is_reported() {
+	let report = testbench::run_testbench(&testbench_of(wrong_steps()), &full_adder_canvas()).expect("Running testbench");
+
+	assert!(!report.is_success());
+	assert_eq!(report.failures.len(), 1);
+
+	let failure = &report.failures[0];
+
+	assert_eq!(failure.step, 4);
+	assert_eq!(failure.tick, 5);
+	assert_eq!(failure.message, "Expected `sum` to be 0 but it was 1");
+
+	for net in [("a", true), ("b", false), ("sum", true), ("cout", false)] {
+		assert!(failure.nets.contains(&(net.0.to_string(), net.1)), "Missing net {net:?} in {:?}", failure.nets);
+	}
+
+	assert!(report.to_string().contains("Step 5 (Tick 5): Expected `sum` to be 0 but it was 1"));
+}
+
+#[test]
+fn unknown_net_is_an_error() {
+	let set = testbench_of(vec![TestStepJson::Set { set: String::from("missing"), value: true }]);
+	assert!(testbench::run_testbench(&set, &full_adder_canvas()).is_err());
+
+	let expect = testbench_of(vec![TestStepJson::Expect { expect: String::from("missing"), value: true }]);
+	assert!(testbench::run_testbench(&expect, &full_adder_canvas()).is_err());
+}
+
+#[test]
+fn failing_testbench_exits_with_error() {
+	let dir = std::env::temp_dir().join(format!("circuit-sim-testbench-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("Creating temp dir");
+
+	// Absolute, so it isn't relative to the temp dir.
+	let mut testbench = testbench_of(wrong_steps());
+	testbench.canvas = Path::new(env!("CARGO_MANIFEST_DIR")).join("testbenches/canvases/full_adder.json").display().to_string();
+
+	let path = dir.join("wrong.json");
+	std::fs::write(&path, serde_json::to_string(&testbench).unwrap()).expect("Writing testbench");
+
+	let output = Command::new(env!("CARGO_BIN_EXE_testbench")).arg(&path).output().expect("Running testbench");
+
+	let _ = std::fs::remove_dir_all(&dir);
+
+	let stdout = String::from_utf8_lossy(&output.stdout);
+
+	assert_eq!(output.status.code(), Some(1), "{}", stdout);
+	assert!(stdout.contains("Wrong Full Adder: 1 failure(s)"), "{}", stdout);
+	assert!(stdout.contains("0 passed, 1 failed"), "{}", stdout);
+}
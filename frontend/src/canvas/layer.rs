@@ -0,0 +1,135 @@
+use std::cell::Cell;
+
+use web_sys::CanvasRenderingContext2d;
+
+use circuit_sim_common::CellPos;
+
+use crate::{Result, editor::ViewOptions};
+
+use super::{Canvas, CellChanges, PixelBackground};
+
+
+/// Color of every other cell of the checkerboard.
+const BACKGROUND_ODD: &str = "#2C2C2C";
+
+
+/// What the cached image was drawn for. It's drawn from scratch once any of it changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LayerView {
+	drag: (f64, f64),
+	pixel_size: usize,
+	size: (usize, usize)
+}
+
+impl From<&ViewOptions> for LayerView {
+	fn from(view: &ViewOptions) -> Self {
+		Self {
+			drag: (view.get_canvas_drag_x(), view.get_canvas_drag_y()),
+			pixel_size: view.pixel_size,
+			size: (view.canvas_width(), view.canvas_height())
+		}
+	}
+}
+
+
+/// Offscreen Canvas which keeps the background and the cells between frames.
+///
+/// Only the cells changed since the last frame are drawn again.
+pub struct StaticLayer {
+	canvas: Canvas,
+
+	drawn_for: Cell<Option<LayerView>>
+}
+
+impl StaticLayer {
+	pub fn new() -> Self {
+		Self {
+			canvas: Canvas::new(),
+			drawn_for: Cell::new(None)
+		}
+	}
+
+	/// Would `draw` have to change the cached image.
+	pub fn is_stale(&self, pixels: &PixelBackground, view: &ViewOptions) -> bool {
+		self.drawn_for.get() != Some(view.into()) || pixels.cells.has_changes()
+	}
+
+	/// Draws the changed cells into the cached image and copies it onto `ctx`.
+	pub fn draw(&self, pixels: &PixelBackground, view: &ViewOptions, ctx: &CanvasRenderingContext2d) -> Result<()> {
+		let layer_view = LayerView::from(view);
+		let changes = pixels.cells.take_changes();
+
+		let drawn_for = self.drawn_for.replace(Some(layer_view));
+
+		if drawn_for.map(|v| v.size) != Some(layer_view.size) {
+			self.canvas.set_width(layer_view.size.0);
+			self.canvas.set_height(layer_view.size.1);
+		}
+
+		let layer = &self.canvas;
+
+		layer.set_transform(1.0, 0.0, 0.0, 1.0, -layer_view.drag.0, -layer_view.drag.1)?;
+
+		let (x, y, width, height) = view.cells_in_view();
+		let (min, max) = ((x, y), (x + width as i32, y + height as i32));
+
+		match changes {
+			CellChanges::Cells(cells) if drawn_for == Some(layer_view) => {
+				let is_inside = |&(x, y): &CellPos| x >= min.0 && y >= min.1 && x <= max.0 && y <= max.1;
+
+				for cell in cells.into_iter().filter(is_inside) {
+					self.draw_cell(cell, pixels, view)?;
+				}
+			}
+
+			_ => {
+				layer.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
+				layer.clear_rect(0.0, 0.0, view.width_f64(), view.height_f64());
+				layer.set_transform(1.0, 0.0, 0.0, 1.0, -layer_view.drag.0, -layer_view.drag.1)?;
+
+				let pixel_size = view.pixel_size as f64;
+
+				layer.set_fill_style_str(BACKGROUND_ODD);
+
+				for cell_x in min.0..=max.0 {
+					for cell_y in min.1..=max.1 {
+						if is_odd_cell((cell_x, cell_y)) {
+							layer.fill_rect(cell_x as f64 * pixel_size, cell_y as f64 * pixel_size, pixel_size, pixel_size);
+						}
+					}
+				}
+
+				for (&(x, y), pixel) in pixels.cells.iter_between(min, max) {
+					pixel.render(x, y, pixel_size, &pixels.palette, layer)?;
+				}
+			}
+		}
+
+		ctx.draw_image_with_html_canvas_element(&self.canvas.element, 0.0, 0.0)?;
+
+		Ok(())
+	}
+
+	fn draw_cell(&self, (x, y): CellPos, pixels: &PixelBackground, view: &ViewOptions) -> Result<()> {
+		let layer = &self.canvas;
+		let pixel_size = view.pixel_size as f64;
+
+		layer.clear_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+
+		if is_odd_cell((x, y)) {
+			layer.set_fill_style_str(BACKGROUND_ODD);
+			layer.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+		}
+
+		if let Some(pixel) = pixels.cells.get(&(x, y)) {
+			pixel.render(x, y, pixel_size, &pixels.palette, layer)?;
+		}
+
+		Ok(())
+	}
+}
+
+
+fn is_odd_cell((x, y): CellPos) -> bool {
+	(x + y + 1).rem_euclid(2) == 1
+}
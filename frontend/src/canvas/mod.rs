@@ -5,7 +5,10 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, Node};
 use wasm_bindgen::JsCast;
 
 
+mod layer;
 mod pixels;
+
+pub use layer::*;
 pub use pixels::*;
 
 
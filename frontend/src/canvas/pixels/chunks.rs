@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+
+use circuit_sim_common::CellPos;
+
+use crate::objects::Renderable;
+
+use super::Pixel;
+
+
+/// Width and height of a chunk in cells.
+pub const CHUNK_SIZE: i32 = 32;
+
+/// Position of a chunk. Chunk (0, 0) holds cells (0, 0) to (31, 31).
+pub type ChunkPos = (i32, i32);
+
+
+pub fn chunk_of((x, y): CellPos) -> ChunkPos {
+	(x.div_euclid(CHUNK_SIZE), y.div_euclid(CHUNK_SIZE))
+}
+
+/// Every chunk which overlaps the rectangle. Both corners are inclusive.
+fn chunks_between(min: CellPos, max: CellPos) -> impl Iterator<Item = ChunkPos> {
+	let (min_x, min_y) = chunk_of(min);
+	let (max_x, max_y) = chunk_of(max);
+
+	(min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+}
+
+
+/// Cells which have to be drawn again.
+#[derive(Default)]
+pub enum CellChanges {
+	#[default]
+	All,
+	Cells(HashSet<CellPos>)
+}
+
+impl CellChanges {
+	pub fn is_empty(&self) -> bool {
+		matches!(self, Self::Cells(cells) if cells.is_empty())
+	}
+}
+
+
+/// Cells grouped into chunks so only the cells in an area have to be looked at.
+#[derive(Default)]
+pub struct ChunkedCells {
+	chunks: HashMap<ChunkPos, HashMap<CellPos, Pixel>>,
+	len: usize,
+
+	/// Every cell which was changed since the cached layer was last drawn.
+	changes: RefCell<CellChanges>
+}
+
+impl ChunkedCells {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	pub fn contains_key(&self, cell: &CellPos) -> bool {
+		self.get(cell).is_some()
+	}
+
+	pub fn get(&self, cell: &CellPos) -> Option<&Pixel> {
+		self.chunks.get(&chunk_of(*cell))?.get(cell)
+	}
+
+	pub fn get_mut(&mut self, cell: &CellPos) -> Option<&mut Pixel> {
+		self.mark_changed(*cell);
+
+		self.chunks.get_mut(&chunk_of(*cell))?.get_mut(cell)
+	}
+
+	pub fn insert(&mut self, cell: CellPos, pixel: Pixel) -> Option<Pixel> {
+		self.mark_changed(cell);
+
+		let previous = self.chunks.entry(chunk_of(cell)).or_default().insert(cell, pixel);
+
+		if previous.is_none() {
+			self.len += 1;
+		}
+
+		previous
+	}
+
+	pub fn remove(&mut self, cell: &CellPos) -> Option<Pixel> {
+		self.mark_changed(*cell);
+
+		let Entry::Occupied(mut chunk) = self.chunks.entry(chunk_of(*cell)) else { return None };
+
+		let removed = chunk.get_mut().remove(cell);
+
+		if removed.is_some() {
+			self.len -= 1;
+		}
+
+		if chunk.get().is_empty() {
+			chunk.remove();
+		}
+
+		removed
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&CellPos, &Pixel)> {
+		self.chunks.values().flat_map(HashMap::iter)
+	}
+
+	pub fn keys(&self) -> impl Iterator<Item = &CellPos> {
+		self.iter().map(|(cell, _)| cell)
+	}
+
+	pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Pixel> {
+		self.mark_all_changed();
+
+		self.chunks.values_mut().flat_map(HashMap::values_mut)
+	}
+
+	/// Cells inside of the rectangle. Both corners are inclusive.
+	pub fn iter_between(&self, min: CellPos, max: CellPos) -> impl Iterator<Item = (&CellPos, &Pixel)> {
+		chunks_between(min, max)
+			.filter_map(move |chunk| self.chunks.get(&chunk))
+			.flat_map(HashMap::iter)
+			.filter(move |(&(x, y), _)| x >= min.0 && y >= min.1 && x <= max.0 && y <= max.1)
+	}
+
+
+	fn mark_changed(&mut self, cell: CellPos) {
+		if let CellChanges::Cells(cells) = self.changes.get_mut() {
+			cells.insert(cell);
+		}
+	}
+
+	/// Every cell has to be drawn again.
+	pub fn mark_all_changed(&mut self) {
+		*self.changes.get_mut() = CellChanges::All;
+	}
+
+	pub fn has_changes(&self) -> bool {
+		!self.changes.borrow().is_empty()
+	}
+
+	/// Changes since it was last called.
+	pub fn take_changes(&self) -> CellChanges {
+		self.changes.replace(CellChanges::Cells(HashSet::new()))
+	}
+}
+
+impl<'a> IntoIterator for &'a ChunkedCells {
+	type Item = (&'a CellPos, &'a Pixel);
+	type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		Box::new(self.iter())
+	}
+}
+
+
+/// Objects bucketed by the chunks they're in. Holds indexes into `PixelBackground::objects`.
+///
+/// Rebuilt the next time it's used after Objects were added or removed. A moved or resized one only has to be `reindex`ed.
+#[derive(Default)]
+pub struct ObjectIndex {
+	buckets: HashMap<ChunkPos, Vec<usize>>,
+	/// Object Id -> Index
+	ids: HashMap<usize, usize>,
+	/// Corners each Object was indexed at. Same order as the Objects.
+	areas: Vec<(CellPos, CellPos)>,
+
+	is_dirty: bool
+}
+
+impl ObjectIndex {
+	pub fn mark_dirty(&mut self) {
+		self.is_dirty = true;
+	}
+
+	/// Rebuilds it if the Objects could have changed since it was last built.
+	pub fn refresh(&mut self, objects: &[Box<dyn Renderable>]) {
+		if !self.is_dirty {
+			return;
+		}
+
+		self.buckets.clear();
+		self.ids.clear();
+		self.areas.clear();
+
+		for (index, object) in objects.iter().enumerate() {
+			let (min, max) = object_area(&**object);
+
+			for chunk in chunks_between(min, max) {
+				self.buckets.entry(chunk).or_default().push(index);
+			}
+
+			self.ids.insert(object.get_id().into(), index);
+			self.areas.push((min, max));
+		}
+
+		self.is_dirty = false;
+	}
+
+	/// Moves a single Object into the chunks it covers now.
+	pub fn reindex(&mut self, index: usize, object: &dyn Renderable) {
+		// Everything is rebuilt on the next refresh anyway.
+		if self.is_dirty {
+			return;
+		}
+
+		let Some(area) = self.areas.get_mut(index) else {
+			self.is_dirty = true;
+			return;
+		};
+
+		for chunk in chunks_between(area.0, area.1) {
+			if let Some(bucket) = self.buckets.get_mut(&chunk) {
+				bucket.retain(|&i| i != index);
+			}
+		}
+
+		*area = object_area(object);
+
+		for chunk in chunks_between(area.0, area.1) {
+			self.buckets.entry(chunk).or_default().push(index);
+		}
+	}
+
+	pub fn index_of_id(&self, id: usize) -> Option<usize> {
+		self.ids.get(&id).copied()
+	}
+
+	/// Objects which could be in the cell.
+	pub fn in_chunk_of(&self, cell: CellPos) -> &[usize] {
+		self.buckets.get(&chunk_of(cell)).map_or(&[], Vec::as_slice)
+	}
+
+	/// Objects which could be inside of the rectangle. Sorted and without duplicates.
+	pub fn between(&self, min: CellPos, max: CellPos) -> Vec<usize> {
+		let found: HashSet<usize> = chunks_between(min, max)
+			.filter_map(|chunk| self.buckets.get(&chunk))
+			.flatten()
+			.copied()
+			.collect();
+
+		let mut found: Vec<usize> = found.into_iter().collect();
+		found.sort_unstable();
+
+		found
+	}
+}
+
+
+/// Corners around the Object and its Nodes.
+fn object_area(object: &dyn Renderable) -> (CellPos, CellPos) {
+	let (x, y) = object.get_cell_pos();
+	let dimensions = object.get_dimensions();
+
+	((x - 1, y - 1), (x + dimensions.width() as i32, y + dimensions.height() as i32))
+}
@@ -110,12 +110,12 @@ impl PixelMap {
 			.enumerate()
 			.filter_map(|(i, t)| {
 				if t.is_some() {
-					let x_offset = i % self.width;
-					let y_offset = i / self.width;
+					let x_offset = (i % self.width) as i32;
+					let y_offset = (i / self.width) as i32;
 
 					Some((
-						(pos.0 + x_offset).checked_sub(1)?,
-						(pos.1 + y_offset).checked_sub(1)?
+						pos.0 + x_offset - 1,
+						pos.1 + y_offset - 1
 					))
 				} else {
 					None
@@ -131,11 +131,11 @@ impl PixelMap {
 			.enumerate()
 			.filter_map(|(i, t)| {
 				if let Some(type_of) = t {
-					let x_offset = i % width;
-					let y_offset = i / width;
+					let x_offset = (i % width) as i32;
+					let y_offset = (i / width) as i32;
 
 					Some((
-						((pos.0 + x_offset).checked_sub(1)?, (pos.1 + y_offset).checked_sub(1)?),
+						(pos.0 + x_offset - 1, pos.1 + y_offset - 1),
 						type_of
 					))
 				} else {
@@ -153,26 +153,26 @@ impl PixelMap {
 			.filter_map(|(i, t)| t.as_ref().map(|t| (i, t)));
 
 		for (i, type_of) in rendering_pixels {
-			let x_offset = i % self.width;
-			let y_offset = i / self.width;
-
-			if let (Some(pos_x), Some(pos_y)) = ((pos.0 + x_offset).checked_sub(1), (pos.1 + y_offset).checked_sub(1)) {
-				let pixel = match type_of {
-					PixelType::Custom(p) => *p,
-					PixelType::Node { .. } => PIXEL_NODE,
-					PixelType::ObjectColor => PIXEL_OBJECT,
-					PixelType::Wire { index, .. } => palette[*index].0
-				};
-
-				ctx.set_fill_style(&JsValue::from_str(&pixel.get_string_color()));
-
-				ctx.fill_rect(
-					pos_x as f64 * pixel_size,
-					pos_y as f64 * pixel_size,
-					pixel_size,
-					pixel_size
-				);
-			}
+			let x_offset = (i % self.width) as i32;
+			let y_offset = (i / self.width) as i32;
+
+			let (pos_x, pos_y) = (pos.0 + x_offset - 1, pos.1 + y_offset - 1);
+
+			let pixel = match type_of {
+				PixelType::Custom(p) => *p,
+				PixelType::Node { .. } => PIXEL_NODE,
+				PixelType::ObjectColor => PIXEL_OBJECT,
+				PixelType::Wire { index, .. } => palette[*index].0
+			};
+
+			ctx.set_fill_style(&JsValue::from_str(&pixel.get_string_color()));
+
+			ctx.fill_rect(
+				pos_x as f64 * pixel_size,
+				pos_y as f64 * pixel_size,
+				pixel_size,
+				pixel_size
+			);
 		}
 
 		Ok(())
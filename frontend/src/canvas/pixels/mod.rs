@@ -1,13 +1,15 @@
-use std::collections::HashMap;
+use std::cell::{Ref, RefCell};
 
-use circuit_sim_common::{CellPos, Side, object::NodeValue};
+use circuit_sim_common::{CellPos, Side, config::DEFAULT_COLOR_PALETTE, object::NodeValue};
 
 use crate::{InnerEditor, Result, editor::Node, ids::ObjectId, objects::Renderable};
 
 
+mod chunks;
 mod map;
 mod util;
 
+pub use chunks::*;
 pub use map::*;
 pub use util::*;
 
@@ -29,62 +31,96 @@ pub static PIXEL_TOOLS: [PixelColor; 1] = [
 
 pub struct PixelBackground {
 	pub palette: Vec<(PixelColor, PixelColor)>,
-	pub cells: HashMap<(usize, usize), Pixel>,
-	pub objects: Vec<Box<dyn Renderable>>
+	pub cells: ChunkedCells,
+	objects: Vec<Box<dyn Renderable>>,
+
+	object_index: RefCell<ObjectIndex>
 }
 
 impl PixelBackground {
 	pub fn new() -> Self {
-		Self {
-			palette: vec![
-				(PixelColor(196, 196, 190), PixelColor(145, 145, 134)),
-				(PixelColor(207, 175, 157), PixelColor(173, 119, 88)),
-				(PixelColor(240, 183, 142), PixelColor(228, 123, 47)),
-				(PixelColor(244, 216, 146), PixelColor(234, 181, 45)),
-				(PixelColor(161, 208, 197), PixelColor(92, 173, 154)),
-				(PixelColor(148, 193, 229), PixelColor(63, 143, 209)),
-				(PixelColor(184, 156, 225), PixelColor(125, 76, 200)),
-				(PixelColor(230, 147, 193), PixelColor(210, 62, 143)),
-				(PixelColor(243, 143, 149), PixelColor(233, 46, 58)),
-			],
-			cells: HashMap::new(),
-			objects: Vec::new()
-		}
+		Self::from_parts(
+			DEFAULT_COLOR_PALETTE.iter()
+				.map(|&(p1, p2)| (p1.into(), p2.into()))
+				.collect(),
+			Vec::new()
+		)
 	}
 
-	pub fn get_surrounding_cells((x, y): CellPos) -> Vec<(CellPos, Side)> {
-		let mut surroundings = vec![((x, y + 1), Side::Bottom), ((x + 1, y), Side::Right)];
+	/// Background without any cells. Call `re_render_objects` once the wires are in.
+	pub fn from_parts(palette: Vec<(PixelColor, PixelColor)>, objects: Vec<Box<dyn Renderable>>) -> Self {
+		let mut object_index = ObjectIndex::default();
+		object_index.mark_dirty();
 
-		if let Some(pos) = x.checked_sub(1).map(|x| (x, y)) {
-			surroundings.push((pos, Side::Left));
-		}
-
-		if let Some(pos) = y.checked_sub(1).map(|y| (x, y)) {
-			surroundings.push((pos, Side::Top));
+		Self {
+			palette,
+			cells: ChunkedCells::new(),
+			objects,
+			object_index: RefCell::new(object_index)
 		}
+	}
 
-		surroundings
+	pub fn get_surrounding_cells((x, y): CellPos) -> Vec<(CellPos, Side)> {
+		vec![
+			((x, y + 1), Side::Bottom),
+			((x + 1, y), Side::Right),
+			((x - 1, y), Side::Left),
+			((x, y - 1), Side::Top)
+		]
 	}
 
+	/// Draws what the Objects show on top of their cells. The cells are drawn by the `StaticLayer`.
 	pub fn render(&self, editor: &InnerEditor) -> Result<()> {
 		let ctx = &editor.canvas;
 
-		let pixel_size = editor.view_opts.pixel_size as f64;
+		let (x, y, width, height) = editor.view_opts.cells_in_view();
+		let (min, max) = ((x, y), (x + width as i32, y + height as i32));
 
-		for (&(x, y), type_of) in &self.cells {
-			type_of.render(x, y, pixel_size, &self.palette, ctx)?;
+		// Labels are drawn above the Objects.
+		for index in self.object_index().between((min.0, min.1 - 1), max) {
+			self.objects[index].render(false, &editor.view_opts, ctx)?;
 		}
 
+		Ok(())
+	}
+
+	pub fn objects(&self) -> &[Box<dyn Renderable>] {
+		&self.objects
+	}
 
-		for obj in &self.objects {
-			obj.render(false, &editor.view_opts, ctx)?;
+	/// Call `reindex_object` after moving or resizing one through this.
+	pub fn objects_mut(&mut self) -> &mut [Box<dyn Renderable>] {
+		&mut self.objects
+	}
+
+	/// Indexes the Object again once it was moved, resized or rotated.
+	pub fn reindex_object(&mut self, id: ObjectId) {
+		let index = self.object_index().index_of_id(id.into());
+
+		if let Some(index) = index {
+			self.object_index.get_mut().reindex(index, &*self.objects[index]);
 		}
+	}
 
-		Ok(())
+	/// Objects which could be inside of the rectangle. Both corners are inclusive.
+	pub fn objects_between(&self, min: CellPos, max: CellPos) -> impl Iterator<Item = &dyn Renderable> {
+		self.object_index()
+			.between(min, max)
+			.into_iter()
+			.map(move |index| &*self.objects[index])
+	}
+
+	fn object_index(&self) -> Ref<'_, ObjectIndex> {
+		self.object_index.borrow_mut().refresh(&self.objects);
+		self.object_index.borrow()
 	}
 
 	//
 
+	pub fn get_pixel_type_from_cell(&self, cell: &CellPos) -> Option<&Pixel> {
+		self.cells.get(cell)
+	}
+
 	pub fn get_pixel_type_from_cell_mut(&mut self, cell: &CellPos) -> Option<&mut Pixel> {
 		self.cells.get_mut(cell)
 	}
@@ -151,9 +187,9 @@ impl PixelBackground {
 
 	// Pixel  Cell
 	pub fn find_connectable_sides(&self, current_pixel_type: &PixelType, (x, y): CellPos) -> (bool, bool, bool, bool) {
-		let top = y.checked_sub(1).map(|y| self.can_connect_to_cell(current_pixel_type, (x, y))).unwrap_or_default();
+		let top = self.can_connect_to_cell(current_pixel_type, (x, y - 1));
 		let bottom = self.can_connect_to_cell(current_pixel_type, (x, y + 1));
-		let left = x.checked_sub(1).map(|x| self.can_connect_to_cell(current_pixel_type, (x, y))).unwrap_or_default();
+		let left = self.can_connect_to_cell(current_pixel_type, (x - 1, y));
 		let right = self.can_connect_to_cell(current_pixel_type, (x + 1, y));
 
 		(top, bottom, left, right)
@@ -263,8 +299,11 @@ impl PixelBackground {
 	pub fn delete_object(&mut self, object_id: ObjectId) {
 		self.delete_object_cells(object_id);
 
-		if let Some(index) = self.objects.iter().position(|o| o.get_id() == object_id) {
+		let index = self.object_index().index_of_id(object_id.into());
+
+		if let Some(index) = index {
 			self.objects.remove(index);
+			self.object_index.get_mut().mark_dirty();
 		}
 	}
 
@@ -292,6 +331,7 @@ impl PixelBackground {
 
 	pub fn add_object(&mut self, object: Box<dyn Renderable>) {
 		self.objects.push(object);
+		self.object_index.get_mut().mark_dirty();
 	}
 
 
@@ -302,36 +342,49 @@ impl PixelBackground {
 
 
 	pub fn get_object_in_cell(&self, cell: CellPos) -> Option<&dyn Renderable> {
-		self.objects.iter().find(|o| o.is_cell_inside(cell)).map(|v| &**v)
+		self.object_index_in_cell(cell).map(|index| &*self.objects[index])
 	}
 
 	pub fn get_object_in_cell_mut(&mut self, cell: CellPos) -> Option<&mut Box<dyn Renderable>> {
-		self.objects.iter_mut().find(|o| o.is_cell_inside(cell))
+		let index = self.object_index_in_cell(cell)?;
+
+		self.objects.get_mut(index)
+	}
+
+	fn object_index_in_cell(&self, cell: CellPos) -> Option<usize> {
+		self.object_index()
+			.in_chunk_of(cell)
+			.iter()
+			.copied()
+			.find(|&index| self.objects[index].is_cell_inside(cell))
 	}
 
 
 	pub fn get_object_by_id(&self, id: ObjectId) -> Option<&dyn Renderable> {
-		self.objects.iter().find(|o| o.get_id() == id).map(|v| &**v)
+		let index = self.object_index().index_of_id(id.into())?;
+
+		self.objects.get(index).map(|v| &**v)
 	}
 
 	pub fn get_object_by_id_mut(&mut self, id: ObjectId) -> Option<&mut Box<dyn Renderable>> {
-		self.objects.iter_mut().find(|o| o.get_id() == id)
+		let index = self.object_index().index_of_id(id.into())?;
+
+		self.objects.get_mut(index)
 	}
 
 
 	pub fn is_valid_object_pos(&self, id: ObjectId) -> bool {
-		let moving = match self.objects.iter().find(|v| v.get_id() == id) {
-			Some(v) => v,
-			None => return true
-		};
+		let Some(moving) = self.get_object_by_id(id) else { return true };
 
-		for obj in &self.objects {
-			if obj.get_id() != id && moving.is_overlapping(&**obj) {
-				return false;
-			}
-		}
+		let (x, y) = moving.get_cell_pos();
+		let dimensions = moving.get_dimensions();
+
+		// Only the Objects close enough to overlap it.
+		let nearby = self.object_index().between((x - 1, y - 1), (x + dimensions.width() as i32, y + dimensions.height() as i32));
 
-		true
+		nearby.into_iter()
+			.map(|index| &self.objects[index])
+			.all(|obj| obj.get_id() == id || !moving.is_overlapping(&**obj))
 	}
 
 	//
@@ -343,7 +396,7 @@ impl PixelBackground {
 			}
 		}
 
-		for object in &mut self.objects {
+		for object in self.objects_mut() {
 			object.reset_values()?;
 		}
 
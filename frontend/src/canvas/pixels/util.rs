@@ -83,7 +83,7 @@ impl PixelDisplay {
 	}
 
 
-	pub fn render(self, cell_x: usize, cell_y: usize, pixel_size: f64, pixel: PixelColor, ctx: &CanvasRenderingContext2d) {
+	pub fn render(self, cell_x: i32, cell_y: i32, pixel_size: f64, pixel: PixelColor, ctx: &CanvasRenderingContext2d) {
 		let inner_size = Self::get_size(pixel_size);
 
 		let re_align = pixel_size / 2.0 - inner_size / 2.0;
@@ -194,7 +194,7 @@ pub struct Pixel {
 }
 
 impl Pixel {
-	pub fn render(&self, cell_x: usize, cell_y: usize, pixel_size: f64, palette: &[(PixelColor, PixelColor)], ctx: &CanvasRenderingContext2d) -> Result<()> {
+	pub fn render(&self, cell_x: i32, cell_y: i32, pixel_size: f64, palette: &[(PixelColor, PixelColor)], ctx: &CanvasRenderingContext2d) -> Result<()> {
 		if self.type_of.is_wire() || self.type_of.is_node() {
 			let pixel = match &self.type_of {
 				PixelType::Wire { index, value } => if value.is_active() {
@@ -1,18 +1,18 @@
 // Saving / Loading
 // Custom Objects / Editors
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use web_sys::{XmlHttpRequest, ProgressEvent};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 
-use circuit_sim_common::{config::{CanvasStateJson, ConfigJson, ObjectNormalJson, ObjectsJson, StateInfo}, http::{CanvasJsonReqResp, LoadResponse, SaveResponse}, object::NodeValue};
+use circuit_sim_common::{CellPos, config::{CanvasStateJson, ConfigJson, ObjectNormalJson, ObjectsJson, StateInfo}, http::{CanvasJsonReqResp, LoadResponse, SaveResponse}, object::NodeValue};
 
-use crate::{NotificationType, Result, canvas::{PixelBackground, PixelType}, editor::state::update_window_location, ids::ObjectId};
+use crate::{Error, NotificationType, Result, canvas::{PixelBackground, PixelColor, PixelType}, editor::{documents::{get_document_mut, is_shown_document}, state::update_window_location}, ids::{DocumentId, ObjectId}, objects::Renderable};
 use crate::error::ConfigError;
 use crate::statics::create_notification;
-use crate::editor::{CanvasState};
+use crate::editor::{CanvasState, drawing::WireMode, signals::Signals, history::{Change, History, apply_change, set_wire_cell}};
 
 
 pub struct Config {
@@ -23,11 +23,23 @@ pub struct Config {
 // Saving into JSON
 
 pub fn save_editor<F>(callback: F) -> Result<()> where F: Fn(bool) + 'static {
-	let canvas_editor = crate::statics::get_editor_state();
+	let canvas_editor = crate::statics::get_editor_state_mut();
+
+	if canvas_editor.is_read_only() {
+		return create_notification("Save", NotificationType::Info(String::from("Fork the Canvas to save a copy of it.")), 1000 * 5);
+	}
+
+	let info = canvas_editor.get_state_info();
+	let canvas = canvas_editor.get_canvas_state_mut().unwrap();
+
+	canvas.history.start_saving();
+
+	// Its tab could've been switched away from by the time it's saved.
+	let document_id = canvas.document_id;
 
 	let json = CanvasJsonReqResp {
-		info: canvas_editor.get_state_info(),
-		json: ConfigJson::new(try_from_editor_state(canvas_editor.get_canvas_state().unwrap())?)
+		info,
+		json: ConfigJson::new(try_from_editor_state(canvas)?)
 	};
 
 	let value = serde_json::to_string(&json)?;
@@ -41,9 +53,22 @@ pub fn save_editor<F>(callback: F) -> Result<()> where F: Fn(bool) + 'static {
 
 				let process = move || -> Result<()> {
 					let state_info = serde_json::from_str::<SaveResponse>(&xhr.response_text()?.unwrap())??;
-					crate::statics::get_editor_state_mut().set_state_info(state_info);
 
-					update_window_location()?;
+					// Its tab was closed.
+					let Some(state) = get_document_mut(document_id) else { return Ok(()) };
+
+					state.set_state_info(state_info);
+
+					if let Some(canvas) = state.get_canvas_state_mut() {
+						canvas.history.finish_saving();
+					}
+
+					if is_shown_document(document_id) {
+						update_window_location()?;
+					}
+
+					// It's titled by its Id until it's given one.
+					crate::statics::get_ui().read()?.topbar.render_tabs()?;
 
 					Ok(())
 				};
@@ -84,31 +109,28 @@ pub fn try_from_editor_state(value: &CanvasState) -> Result<CanvasStateJson> {
 	Ok(CanvasStateJson {
 		pixels,
 
-		objects: value.pixels.objects.iter().map(|v| {
-			ObjectsJson::Normal(ObjectNormalJson {
-				id: v.get_id().into(),
-				type_of: v.get_object_state().type_of,
-				pos: v.get_cell_pos(),
-				dim: v.get_dimensions(),
-				nodes: v.get_object_state().nodes.iter().map(|n| n.into()).collect()
-			})
-		}).collect(),
+		objects: value.pixels.objects().iter().map(|v| ObjectsJson::Normal(object_to_json(&**v))).collect(),
 		text_objects: Vec::new(),
+		bookmarks: value.bookmarks.clone(),
 
-		color_palette: value.pixels.palette.iter()
-			.copied()
-			.fold(
-				Vec::new(),
-				|mut vec, (p1, p2)| {
-					vec.push(p1.into());
-					vec.push(p2.into());
-
-					vec
-				}
-			)
+		color_palette: palette_to_json(&value.pixels.palette)
 	})
 }
 
+pub fn palette_to_json(palette: &[(PixelColor, PixelColor)]) -> Vec<(u16, u16, u16)> {
+	palette.iter()
+		.copied()
+		.fold(
+			Vec::new(),
+			|mut vec, (p1, p2)| {
+				vec.push(p1.into());
+				vec.push(p2.into());
+
+				vec
+			}
+		)
+}
+
 
 
 
@@ -159,25 +181,34 @@ pub fn create_config_from_json(json: CanvasJsonReqResp) -> Result<CanvasState> {
 pub fn create_state_from_json(editor: CanvasStateJson, info: StateInfo) -> Result<CanvasState> {
 	let palette = editor.color_palette.chunks(2).map(|val| (val[0].into(), val[1].into())).collect();
 
+	// Saved Ids are kept so anything referencing an Object still finds it.
+	let last_id = editor.objects.iter()
+		.filter_map(|object| if let ObjectsJson::Normal(json) = object { Some(json.id) } else { None })
+		.max()
+		.unwrap_or_default();
+
+	ObjectId::seed(last_id);
+
+	let mut used_ids = HashSet::new();
+
 	let objects = editor.objects.into_iter()
-		.map(|o| {
-			match o {
-				ObjectsJson::Normal(json) => {
-					let mut object = crate::objects::create_new_object(ObjectId::gen_id(), json.type_of, json.pos, Some(json.dim));
-					object.get_object_state_mut().nodes = json.nodes.into_iter().map(|node| node.into()).collect();
-					object
-				}
+		.map(|object| match object {
+			ObjectsJson::Normal(json) => {
+				// Zero is the empty Id. Duplicates could only come from a hand edited save.
+				let id = if json.id != 0 && used_ids.insert(json.id) {
+					ObjectId(json.id)
+				} else {
+					ObjectId::gen_id()
+				};
 
-				_ => unimplemented!()
+				create_object_from_normal_json(id, json)
 			}
+
+			object => create_object_from_json(object)
 		})
 		.collect();
 
-	let mut background = PixelBackground {
-		cells: HashMap::new(),
-		palette,
-		objects
-	};
+	let mut background = PixelBackground::from_parts(palette, objects);
 
 
 	for (index, poses) in editor.pixels {
@@ -194,6 +225,115 @@ pub fn create_state_from_json(editor: CanvasStateJson, info: StateInfo) -> Resul
 	Ok(CanvasState {
 		info,
 		event: None,
-		pixels: background
+		wire_mode: WireMode::default(),
+		pixels: background,
+		highlighted: Vec::new(),
+		critical_path: Vec::new(),
+		critical_path_revision: 0,
+		highlighted_net: None,
+		bookmarks: editor.bookmarks,
+		signals: Signals::new(),
+		history: History::new(),
+		document_id: DocumentId::gen_id(),
+		moving_from: None
 	})
-}
\ No newline at end of file
+}
+
+pub fn object_to_json(object: &dyn Renderable) -> ObjectNormalJson {
+	ObjectNormalJson {
+		id: object.get_id().into(),
+		type_of: object.get_object_state().type_of,
+		pos: object.get_cell_pos(),
+		dim: object.get_dimensions(),
+		nodes: object.get_object_state().nodes.iter().map(|n| n.into()).collect(),
+		orientation: object.get_object_state().get_orientation(),
+		settings: object.export_settings()
+	}
+}
+
+/// Creates the Object with a new Id. Used when it's inserted next to other Objects.
+pub fn create_object_from_json(object: ObjectsJson) -> Box<dyn Renderable> {
+	match object {
+		ObjectsJson::Normal(json) => create_object_from_normal_json(ObjectId::gen_id(), json),
+
+		_ => unimplemented!()
+	}
+}
+
+pub fn create_object_from_normal_json(id: ObjectId, json: ObjectNormalJson) -> Box<dyn Renderable> {
+	let mut object = crate::objects::create_new_object(id, json.type_of, json.pos, Some(json.dim));
+	object.get_object_state_mut().nodes = json.nodes.into_iter().map(|node| node.into()).collect();
+	object.get_object_state_mut().set_orientation(json.orientation);
+
+	if let Err(e) = object.import_settings(json.settings) {
+		log!("Unable to import the Object settings: {:?}", e);
+	}
+
+	object
+}
+
+
+/// Inserts the Objects and Wires into the canvas, to the right of everything already placed on it.
+///
+/// Wire colors are matched to the canvas palette. Missing colors are appended to it.
+pub fn insert_state_json(canvas: &mut CanvasState, editor: CanvasStateJson) -> Result<()> {
+	let offset_x = canvas.pixels.cells.keys()
+		.map(|(x, _)| x + 1)
+		.max()
+		.unwrap_or_default();
+
+	insert_state_json_at(canvas, editor, (offset_x, 0))?;
+
+	Ok(())
+}
+
+/// Inserts the Objects and Wires moved by `offset` as a single history step. Wires under the inserted Objects are removed.
+///
+/// Returns the inserted Objects and Wire cells.
+pub fn insert_state_json_at(canvas: &mut CanvasState, editor: CanvasStateJson, (offset_x, offset_y): CellPos) -> Result<(Vec<ObjectId>, Vec<CellPos>)> {
+	let palette_indexes: Vec<usize> = editor.color_palette.chunks(2)
+		.map(|val| {
+			let pair: (PixelColor, PixelColor) = (val[0].into(), val[1].into());
+
+			if let Some(index) = canvas.pixels.palette.iter().position(|p| p.0 == pair.0) {
+				index
+			} else {
+				canvas.pixels.palette.push(pair);
+				canvas.pixels.palette.len() - 1
+			}
+		})
+		.collect();
+
+	let mut changes = Vec::new();
+	let mut objects = Vec::new();
+	let mut wires = Vec::new();
+
+	for (index, poses) in editor.pixels {
+		let index = palette_indexes.get(index).copied().ok_or_else(|| Error::String(format!("Unknown palette index {index}")))?;
+
+		for (x, y) in poses {
+			set_wire_cell(&mut canvas.pixels, &mut changes, (x + offset_x, y + offset_y), Some(index));
+			wires.push((x + offset_x, y + offset_y));
+		}
+	}
+
+	for json in editor.objects {
+		let mut object = create_object_from_json(json);
+
+		let (x, y) = object.get_cell_pos();
+		object.set_cell_pos((x + offset_x, y + offset_y));
+
+		for cell in object.pixel_map().pixel_positions(object.get_cell_pos()) {
+			set_wire_cell(&mut canvas.pixels, &mut changes, cell, None);
+		}
+
+		wires.retain(|&c| !object.is_cell_inside(c));
+		objects.push(object.get_id());
+
+		apply_change(&mut canvas.pixels, &mut changes, Change::AddObject(object_to_json(&*object)));
+	}
+
+	canvas.history.push_step(changes);
+
+	Ok((objects, wires))
+}
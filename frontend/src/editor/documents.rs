@@ -0,0 +1,288 @@
+use std::mem;
+
+use wasm_bindgen::JsValue;
+use web_sys::HtmlSpanElement;
+
+use crate::{
+	Result, config, window,
+	ids::DocumentId,
+	objects::ObjectData,
+	ui::popup::Popup,
+	statics::{self, get_documents, get_editor_state, get_editor_state_mut, is_editor_running, replace_editor_state, replace_ticking, set_editor_running}
+};
+
+use super::{CanvasState, EditorState, ViewOptions, state::viewer::remove_viewing_classes};
+
+
+/// A document which isn't shown. Keeps what the editor had while it was.
+struct ParkedDocument {
+	state: Box<dyn EditorState>,
+	view_opts: ViewOptions,
+
+	is_running: bool,
+	/// Ticks which were queued while it was running.
+	ticking: Vec<ObjectData>
+}
+
+
+/// Open documents. Shown as tabs in the Topbar.
+pub struct Documents {
+	/// `None` is the shown document. It's in `EDITOR_STATE`.
+	tabs: Vec<Option<ParkedDocument>>,
+	active: usize
+}
+
+impl Documents {
+	pub fn new() -> Self {
+		Self {
+			tabs: vec![None],
+			active: 0
+		}
+	}
+
+	/// Index of the shown document.
+	pub fn active(&self) -> usize {
+		self.active
+	}
+
+	/// Title of every document in tab order.
+	pub fn titles(&self) -> Vec<String> {
+		self.tabs.iter()
+			.map(|tab| document_title(state_of(tab.as_ref())))
+			.collect()
+	}
+
+	/// Tab of the saved Canvas if it's already open.
+	fn find_canvas(&self, canvas_id: &str) -> Option<usize> {
+		self.tabs.iter().position(|tab| {
+			state_of(tab.as_ref()).get_canvas_state().is_some_and(|canvas| canvas.info.canvas_id.as_deref() == Some(canvas_id))
+		})
+	}
+}
+
+fn state_of(tab: Option<&ParkedDocument>) -> &dyn EditorState {
+	match tab {
+		Some(document) => &*document.state,
+		None => get_editor_state()
+	}
+}
+
+
+/// Opens `state` in a new tab and shows it.
+pub fn open_document(state: Box<dyn EditorState>) -> Result<()> {
+	if !can_leave_shown() {
+		return Ok(());
+	}
+
+	let parked = show(state, None, false, Vec::new())?;
+
+	let documents = get_documents();
+
+	documents.tabs[documents.active] = Some(parked);
+	documents.tabs.push(None);
+	documents.active = documents.tabs.len() - 1;
+
+	finish_switching()
+}
+
+/// Loads the saved Canvas into a new tab. Shows its tab instead if it's already open.
+pub fn open_saved_canvas(canvas_id: String) -> Result<()> {
+	if let Some(index) = get_documents().find_canvas(&canvas_id) {
+		return switch_document(index);
+	}
+
+	config::load(canvas_id, |result| {
+		match result {
+			Some(json) => open_document(Box::new(config::create_config_from_json(json)?)),
+			None => Ok(())
+		}
+	})
+}
+
+/// Shows the document in the tab. The shown one keeps its unsaved changes.
+pub fn switch_document(index: usize) -> Result<()> {
+	let documents = get_documents();
+
+	if index == documents.active || index >= documents.tabs.len() || !can_leave_shown() {
+		return Ok(());
+	}
+
+	let Some(document) = documents.tabs[index].take() else { return Ok(()) };
+
+	let parked = show(document.state, Some(document.view_opts), document.is_running, document.ticking)?;
+
+	documents.tabs[documents.active] = Some(parked);
+	documents.active = index;
+
+	finish_switching()
+}
+
+/// Closes the tab. Asks first if the Canvas was edited. The last tab can't be closed.
+pub fn close_document(index: usize, is_confirmed: bool) -> Result<()> {
+	let documents = get_documents();
+
+	if documents.tabs.len() == 1 || index >= documents.tabs.len() {
+		return Ok(());
+	}
+
+	if !is_confirmed {
+		let state = state_of(documents.tabs[index].as_ref());
+
+		if state.get_canvas_state().is_some_and(CanvasState::is_edited) {
+			return confirm_close_popup(index, document_title(state));
+		}
+	}
+
+	if index == documents.active {
+		switch_document(if index == 0 { 1 } else { index - 1 })?;
+
+		// It's still loading.
+		if index == documents.active {
+			return Ok(());
+		}
+	}
+
+	documents.tabs.remove(index);
+
+	if index < documents.active {
+		documents.active -= 1;
+	}
+
+	statics::get_ui().read()?.topbar.render_tabs()
+}
+
+
+/// The shown or parked document with the Canvas.
+pub fn get_document_mut<'a>(document_id: DocumentId) -> Option<&'a mut dyn EditorState> {
+	if is_shown_document(document_id) {
+		return Some(get_editor_state_mut());
+	}
+
+	let document = get_documents().tabs.iter_mut()
+		.flatten()
+		.find(|document| document.state.get_canvas_state().is_some_and(|canvas| canvas.document_id == document_id))?;
+
+	Some(&mut *document.state)
+}
+
+pub fn is_shown_document(document_id: DocumentId) -> bool {
+	get_editor_state().get_canvas_state().is_some_and(|canvas| canvas.document_id == document_id)
+}
+
+
+/// A Canvas which is still loading can't be parked. Its request finishes into the shown state.
+fn can_leave_shown() -> bool {
+	get_editor_state().get_canvas_state().is_some()
+}
+
+/// Replaces the shown document with `state`. Returns the one which was shown.
+fn show(state: Box<dyn EditorState>, view_opts: Option<ViewOptions>, is_running: bool, ticking: Vec<ObjectData>) -> Result<ParkedDocument> {
+	let editor = statics::get_ui().read()?.editor.clone();
+
+	let view_opts = {
+		let mut editor = editor.write()?;
+
+		let (width, height) = (editor.view_opts.canvas_width(), editor.view_opts.canvas_height());
+
+		let mut view_opts = view_opts.unwrap_or_else(|| ViewOptions::new(width, height));
+
+		// The window could've been resized while it was parked.
+		view_opts.set_width(width);
+		view_opts.set_height(height);
+
+		// Its netlist could match the other document's revision and counts.
+		editor.inspector.reset();
+
+		mem::replace(&mut editor.view_opts, view_opts)
+	};
+
+	let was_running = is_editor_running();
+	set_editor_running(is_running);
+
+	let ticking = replace_ticking(ticking);
+
+	remove_viewing_classes()?;
+
+	let state = replace_editor_state(state)?;
+
+	Ok(ParkedDocument {
+		state,
+		view_opts,
+		is_running: was_running,
+		ticking
+	})
+}
+
+fn finish_switching() -> Result<()> {
+	if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+		// The cached cells were drawn for the previous document.
+		canvas.pixels.cells.mark_all_changed();
+	}
+
+	update_location()?;
+
+	statics::get_ui().read()?.topbar.render_tabs()
+}
+
+/// Puts the shown document into the address bar.
+fn update_location() -> Result<()> {
+	let state = get_editor_state();
+
+	let Some(canvas) = state.get_canvas_state() else { return Ok(()) };
+
+	let url = match &canvas.info.canvas_id {
+		Some(id) if state.is_read_only() => format!("/view/{id}"),
+		Some(id) => format!("/edit?{}={id}", state.search_name()),
+		None => String::from("/edit")
+	};
+
+	window().history()?.replace_state_with_url(&JsValue::NULL, "", Some(&url))?;
+
+	Ok(())
+}
+
+fn document_title(state: &dyn EditorState) -> String {
+	let Some(canvas) = state.get_canvas_state() else { return String::from("Loading") };
+
+	let title = canvas.info.title.clone()
+		.or_else(|| canvas.info.canvas_id.clone())
+		.unwrap_or_else(|| String::from("Untitled"));
+
+	if state.is_read_only() {
+		format!("{title} (Viewing)")
+	} else {
+		title
+	}
+}
+
+fn confirm_close_popup(index: usize, title: String) -> Result<()> {
+	let popup = Popup::new("Close Tab?")?;
+
+	{
+		let row = popup.add_row()?;
+
+		let info = crate::create_element::<HtmlSpanElement>("span");
+		info.set_inner_text(&format!("\"{title}\" was edited. Closing it loses any changes which weren't saved."));
+		row.append_with_node_1(&info)?;
+	}
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Close Anyway", move |_| {
+			popup_copied.close();
+
+			if let Err(e) = close_document(index, true) {
+				log!("Close Tab Error: {:?}", e);
+			}
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Cancel", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,108 @@
+use circuit_sim_common::CellPos;
+
+
+/// How dragging while painting lays down wires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WireMode {
+	/// Every cell the cursor is dragged over.
+	#[default]
+	Freehand,
+	/// Horizontal or vertical run along whichever axis the cursor moved the most.
+	Line,
+	/// Horizontal and vertical run joined by a single bend.
+	LShape {
+		vertical_first: bool
+	},
+	/// Outline of the rectangle between the two cells.
+	Rectangle
+}
+
+impl WireMode {
+	pub fn list() -> [Self; 4] {
+		[Self::Freehand, Self::Line, Self::LShape { vertical_first: false }, Self::Rectangle]
+	}
+
+	pub fn title(self) -> &'static str {
+		match self {
+			Self::Freehand => "Freehand",
+			Self::Line => "Line",
+			Self::LShape { .. } => "L-Shape",
+			Self::Rectangle => "Rectangle"
+		}
+	}
+
+	/// Same mode, ignoring the bend direction.
+	pub fn is_same_mode(self, other: Self) -> bool {
+		std::mem::discriminant(&self) == std::mem::discriminant(&other)
+	}
+
+	/// Next mode in `list`. The bend direction is kept.
+	#[must_use]
+	pub fn next(self) -> Self {
+		match self {
+			Self::Freehand => Self::Line,
+			Self::Line => Self::LShape { vertical_first: false },
+			Self::LShape { .. } => Self::Rectangle,
+			Self::Rectangle => Self::Freehand
+		}
+	}
+
+	/// Switches which run of the L-Shape comes first.
+	#[must_use]
+	pub fn flipped_bend(self) -> Self {
+		match self {
+			Self::LShape { vertical_first } => Self::LShape { vertical_first: !vertical_first },
+			mode => mode
+		}
+	}
+
+	/// Cells painted by a drag from `start` to `end`. Empty for Freehand since it paints while dragging.
+	pub fn cells(self, start: CellPos, end: CellPos) -> Vec<CellPos> {
+		let mut cells = match self {
+			Self::Freehand => return Vec::new(),
+
+			Self::Line => {
+				if start.0.abs_diff(end.0) >= start.1.abs_diff(end.1) {
+					run_horizontal(start.1, start.0, end.0)
+				} else {
+					run_vertical(start.0, start.1, end.1)
+				}
+			}
+
+			Self::LShape { vertical_first: false } => {
+				let mut cells = run_horizontal(start.1, start.0, end.0);
+				cells.append(&mut run_vertical(end.0, start.1, end.1));
+				cells
+			}
+
+			Self::LShape { vertical_first: true } => {
+				let mut cells = run_vertical(start.0, start.1, end.1);
+				cells.append(&mut run_horizontal(end.1, start.0, end.0));
+				cells
+			}
+
+			Self::Rectangle => {
+				let mut cells = run_horizontal(start.1, start.0, end.0);
+				cells.append(&mut run_horizontal(end.1, start.0, end.0));
+				cells.append(&mut run_vertical(start.0, start.1, end.1));
+				cells.append(&mut run_vertical(end.0, start.1, end.1));
+				cells
+			}
+		};
+
+		// Corners are shared between runs.
+		cells.sort_unstable();
+		cells.dedup();
+
+		cells
+	}
+}
+
+
+fn run_horizontal(y: i32, from_x: i32, to_x: i32) -> Vec<CellPos> {
+	(from_x.min(to_x)..=from_x.max(to_x)).map(|x| (x, y)).collect()
+}
+
+fn run_vertical(x: i32, from_y: i32, to_y: i32) -> Vec<CellPos> {
+	(from_y.min(to_y)..=from_y.max(to_y)).map(|y| (x, y)).collect()
+}
@@ -0,0 +1,163 @@
+use circuit_sim_common::{CellPos, netlist::NodeRef};
+
+use crate::canvas::PixelBackground;
+use crate::editor::net;
+use crate::objects::Renderable;
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DesignRule {
+	UnconnectedInput,
+	FloatingWire,
+	OutputToOutput,
+	IncompatibleValueTypes,
+	OverlappingObjects
+}
+
+impl DesignRule {
+	pub fn title(self) -> &'static str {
+		match self {
+			Self::UnconnectedInput => "Unconnected Input",
+			Self::FloatingWire => "Floating Wire",
+			Self::OutputToOutput => "Output to Output",
+			Self::IncompatibleValueTypes => "Incompatible Values",
+			Self::OverlappingObjects => "Overlapping Objects"
+		}
+	}
+}
+
+
+/// A broken design rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+	pub rule: DesignRule,
+	pub description: String,
+
+	/// Cells to highlight on the Canvas.
+	pub cells: Vec<CellPos>
+}
+
+impl Finding {
+	fn new(rule: DesignRule, description: String, cells: Vec<CellPos>) -> Self {
+		Self { rule, description, cells }
+	}
+}
+
+
+/// Checks the Canvas against every `DesignRule`.
+pub fn check_design(pixels: &PixelBackground) -> Vec<Finding> {
+	let mut findings = Vec::new();
+
+	let objects = pixels.objects();
+
+	let object_nodes = net::object_nodes(pixels);
+
+	for (i, object) in objects.iter().enumerate() {
+		for other in &objects[i + 1..] {
+			if object.is_overlapping(&**other) {
+				let mut cells = object.pixel_map().pixel_positions(object.get_cell_pos());
+				cells.append(&mut other.pixel_map().pixel_positions(other.get_cell_pos()));
+
+				findings.push(Finding::new(
+					DesignRule::OverlappingObjects,
+					format!("{} overlaps {}", describe_object(&**object), describe_object(&**other)),
+					cells
+				));
+			}
+		}
+	}
+
+
+	let netlist = net::netlist(pixels, &object_nodes);
+
+	let node = |(object, index): NodeRef| &object_nodes[object][index];
+
+	let describe_node = |node_ref: NodeRef| {
+		let value = node(node_ref);
+
+		format!(
+			"{} {} at ({}, {})",
+			objects[node_ref.0].display_name(),
+			if value.is_output() { "output" } else { "input" },
+			value.pos.0,
+			value.pos.1
+		)
+	};
+
+	for net in &netlist.nets {
+		let net_cells = |node_refs: &[NodeRef]| -> Vec<CellPos> {
+			node_refs.iter()
+				.map(|&n| node(n).pos)
+				.chain(net.wires.iter().copied())
+				.collect()
+		};
+
+		if net.nodes.is_empty() {
+			if let Some(&(x, y)) = net.wires.first() {
+				findings.push(Finding::new(
+					DesignRule::FloatingWire,
+					format!("Wire at ({x}, {y}) isn't touching any node"),
+					net.wires.clone()
+				));
+			}
+
+			continue;
+		}
+
+		let (outputs, inputs): (Vec<NodeRef>, Vec<NodeRef>) = net.nodes.iter().partition(|&&n| node(n).is_output());
+
+		if outputs.len() > 1 {
+			let names: Vec<String> = outputs.iter().map(|&n| describe_node(n)).collect();
+
+			findings.push(Finding::new(
+				DesignRule::OutputToOutput,
+				format!("Outputs are wired together: {}", names.join(", ")),
+				net_cells(&outputs)
+			));
+		}
+
+		// `GpioOrCurrent` is compatible with both others so every pair has to be compared.
+		let incompatible = net.nodes.iter()
+			.enumerate()
+			.find_map(|(i, &a)| {
+				net.nodes[i + 1..].iter()
+					.find(|&&b| node(a).accepts != node(b).accepts)
+					.map(|&b| (a, b))
+			});
+
+		if let Some((a, b)) = incompatible {
+			findings.push(Finding::new(
+				DesignRule::IncompatibleValueTypes,
+				format!(
+					"{} ({:?}) is connected to {} ({:?})",
+					describe_node(a),
+					node(a).accepts,
+					describe_node(b),
+					node(b).accepts
+				),
+				net_cells(&net.nodes)
+			));
+		}
+
+		if outputs.is_empty() {
+			for input in inputs {
+				findings.push(Finding::new(
+					DesignRule::UnconnectedInput,
+					format!("{} isn't driven by any output", describe_node(input)),
+					net_cells(&[input])
+				));
+			}
+		}
+	}
+
+	findings
+}
+
+
+fn describe_object(object: &dyn Renderable) -> String {
+	let (x, y) = object.get_cell_pos();
+
+	format!("{} at ({x}, {y})", object.display_name())
+}
+
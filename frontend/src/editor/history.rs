@@ -0,0 +1,404 @@
+use circuit_sim_common::{CellPos, config::ObjectNormalJson, object::{NodeValue, ObjectType}};
+
+use crate::{canvas::{PixelBackground, PixelType}, config::create_object_from_normal_json, ids::ObjectId};
+
+
+/// Oldest steps are dropped after this.
+const MAX_STEPS: usize = 200;
+
+
+/// A single reversible edit of the Canvas.
+#[derive(Debug, Clone)]
+pub enum Change {
+	/// Palette index of the wire before and after. `None` if the cell didn't contain a wire.
+	Wire {
+		cell: CellPos,
+		before: Option<usize>,
+		after: Option<usize>
+	},
+
+	MoveObject {
+		id: ObjectId,
+		type_of: ObjectType,
+		from: CellPos,
+		to: CellPos
+	},
+
+	AddObject(ObjectNormalJson),
+	DeleteObject(ObjectNormalJson),
+
+	/// Dimensions, Nodes or orientation of an Object changed. The Object stays where it currently is.
+	EditObject {
+		before: ObjectNormalJson,
+		after: ObjectNormalJson
+	}
+}
+
+impl Change {
+	#[must_use]
+	pub fn inverse(&self) -> Self {
+		match self.clone() {
+			Self::Wire { cell, before, after } => Self::Wire { cell, before: after, after: before },
+			Self::MoveObject { id, type_of, from, to } => Self::MoveObject { id, type_of, from: to, to: from },
+			Self::AddObject(json) => Self::DeleteObject(json),
+			Self::DeleteObject(json) => Self::AddObject(json),
+			Self::EditObject { before, after } => Self::EditObject { before: after, after: before }
+		}
+	}
+
+	pub fn apply(&self, pixels: &mut PixelBackground) {
+		match self {
+			&Self::Wire { cell, after, .. } => {
+				// Never replace an Object cell.
+				if pixels.cells.get(&cell).is_some_and(|p| !p.type_of.is_wire()) {
+					return;
+				}
+
+				match after {
+					Some(index) => pixels.insert_cell(cell, PixelType::Wire { index, value: NodeValue::Gpio(false) }),
+					None => pixels.delete_cell(cell)
+				}
+			}
+
+			&Self::MoveObject { id, to, .. } => {
+				let Some(object) = pixels.get_object_by_id(id) else { return };
+
+				let previous = object.pixel_map().pixel_positions(object.get_cell_pos());
+
+				pixels.delete_object_cells(id);
+
+				if let Some(object) = pixels.get_object_by_id_mut(id) {
+					object.set_cell_pos(to);
+				}
+
+				pixels.reindex_object(id);
+
+				pixels.insert_object_cells_by_id(id);
+
+				// Objects moved as a group can overlap while they're moved one by one. Put back the cells of the ones which were covered.
+				let covered: Vec<ObjectId> = pixels.objects().iter()
+					.filter(|o| o.get_id() != id)
+					.filter(|o| o.pixel_map().pixel_positions(o.get_cell_pos()).iter().any(|c| previous.contains(c)))
+					.map(|o| o.get_id())
+					.collect();
+
+				for id in covered {
+					pixels.insert_object_cells_by_id(id);
+				}
+			}
+
+			Self::AddObject(json) => {
+				let id = ObjectId(json.id);
+
+				if pixels.get_object_by_id(id).is_none() {
+					pixels.add_object(create_object_from_normal_json(id, json.clone()));
+					pixels.insert_object_cells_by_id(id);
+				}
+			}
+
+			Self::DeleteObject(json) => {
+				let id = ObjectId(json.id);
+
+				if pixels.get_object_by_id(id).is_some() {
+					pixels.delete_object(id);
+				}
+			}
+
+			Self::EditObject { after, .. } => {
+				let id = ObjectId(after.id);
+
+				let Some(object) = pixels.get_object_by_id(id) else { return };
+
+				let pos = object.get_cell_pos();
+
+				pixels.delete_object_cells(id);
+
+				let mut json = after.clone();
+				json.pos = pos;
+
+				if let Some(object) = pixels.get_object_by_id_mut(id) {
+					*object = create_object_from_normal_json(id, json);
+				}
+
+				pixels.reindex_object(id);
+
+				pixels.insert_object_cells_by_id(id);
+			}
+		}
+	}
+}
+
+
+/// Sets or removes the wire of a cell and adds what happened into `changes`.
+pub fn set_wire_cell(pixels: &mut PixelBackground, changes: &mut Vec<Change>, cell: CellPos, after: Option<usize>) {
+	let before = pixels.cells.get(&cell).and_then(|p| p.type_of.get_wire_palette_index());
+
+	if before == after {
+		return;
+	}
+
+	let change = Change::Wire { cell, before, after };
+	change.apply(pixels);
+	changes.push(change);
+}
+
+/// Applies a change and adds it into `changes`.
+pub fn apply_change(pixels: &mut PixelBackground, changes: &mut Vec<Change>, change: Change) {
+	change.apply(pixels);
+	changes.push(change);
+}
+
+
+/// Changes which are undone and redone together.
+#[derive(Debug, Clone, Default)]
+pub struct Step {
+	pub changes: Vec<Change>
+}
+
+impl Step {
+	pub fn description(&self) -> String {
+		let objects: Vec<&Change> = self.changes.iter().filter(|c| !matches!(c, Change::Wire { .. })).collect();
+
+		match objects.as_slice() {
+			[] => (),
+
+			[Change::MoveObject { type_of, .. }] => return format!("Move {type_of:?}"),
+			[Change::AddObject(json)] => return format!("Add {:?}", json.type_of),
+			[Change::DeleteObject(json)] => return format!("Delete {:?}", json.type_of),
+
+			[Change::EditObject { before, after }] => {
+				let action = if before.orientation.mirrored != after.orientation.mirrored {
+					"Mirror"
+				} else if before.orientation != after.orientation {
+					"Rotate"
+				} else if before.dim != after.dim {
+					"Resize"
+				} else {
+					"Edit"
+				};
+
+				return format!("{action} {:?}", after.type_of);
+			}
+
+			v if v.iter().all(|c| matches!(c, Change::AddObject(_))) => return format!("Add {} Objects", v.len()),
+			v => return format!("Change {} Objects", v.len())
+		}
+
+		let painted = self.changes.iter().filter(|c| matches!(c, Change::Wire { after: Some(_), .. })).count();
+		let erased = self.changes.len() - painted;
+
+		match (painted, erased) {
+			(v, 0) => format!("Paint {v} Wire{}", if v == 1 { "" } else { "s" }),
+			(0, v) => format!("Erase {v} Wire{}", if v == 1 { "" } else { "s" }),
+			(p, e) => format!("Edit {} Wires", p + e)
+		}
+	}
+}
+
+
+/// Undo/Redo stack of the Canvas.
+#[derive(Debug, Default)]
+pub struct History {
+	steps: Vec<Step>,
+
+	/// Amount of steps which are applied. The ones after it can be redone.
+	position: usize,
+
+	/// Last step keeps receiving changes until it's closed. Used to merge drags into one step.
+	is_open: bool,
+
+	/// Incremented on every change. Lets the UI know when to re-render.
+	revision: usize,
+
+	/// Position it was loaded or last saved at. `None` once the steps up to it were replaced, or if it was never saved.
+	saved_position: Option<usize>,
+	/// Position of the save which hasn't finished yet.
+	saving_position: Option<usize>
+}
+
+impl History {
+	/// History of a Canvas which was just loaded. Nothing is lost until it's edited.
+	pub fn new() -> Self {
+		Self {
+			saved_position: Some(0),
+			..Self::default()
+		}
+	}
+
+	pub fn steps(&self) -> &[Step] {
+		&self.steps
+	}
+
+	pub fn position(&self) -> usize {
+		self.position
+	}
+
+	pub fn revision(&self) -> usize {
+		self.revision
+	}
+
+	pub fn can_undo(&self) -> bool {
+		self.position != 0
+	}
+
+	pub fn can_redo(&self) -> bool {
+		self.position < self.steps.len()
+	}
+
+	/// Are the applied steps the ones it was loaded or last saved with.
+	pub fn is_saved(&self) -> bool {
+		self.saved_position == Some(self.position)
+	}
+
+	/// Forgets where it was saved. Ex: A fork which only exists in its tab.
+	pub fn clear_saved(&mut self) {
+		self.saved_position = None;
+		self.saving_position = None;
+	}
+
+	/// Remembers what's being saved. Changes after it go into a new step.
+	pub fn start_saving(&mut self) {
+		self.close();
+
+		self.saving_position = Some(self.position);
+	}
+
+	/// The save which was started last finished.
+	pub fn finish_saving(&mut self) {
+		if let Some(position) = self.saving_position.take() {
+			self.saved_position = Some(position);
+		}
+	}
+
+
+	/// Adds a change which was already applied into the open step. Opens a new step if there isn't one.
+	///
+	/// Wire changes of the same cell are merged.
+	pub fn record(&mut self, change: Change) {
+		if !self.is_open {
+			self.start_step(Vec::new());
+			self.is_open = true;
+		}
+
+		let Some(Step { changes }) = self.steps.last_mut() else { return };
+
+		if let Change::Wire { cell, after, .. } = change {
+			let existing = changes.iter().position(|c| matches!(c, &Change::Wire { cell: v, .. } if v == cell));
+
+			if let Some(index) = existing {
+				if let Change::Wire { before, after: last, .. } = &mut changes[index] {
+					*last = after;
+
+					if *before == after {
+						changes.remove(index);
+					}
+				}
+
+				self.revision += 1;
+
+				return;
+			}
+		}
+
+		changes.push(change);
+
+		self.revision += 1;
+	}
+
+	/// Adds changes which were already applied as their own step.
+	pub fn push_step(&mut self, changes: Vec<Change>) {
+		self.close();
+
+		if changes.is_empty() {
+			return;
+		}
+
+		self.start_step(changes);
+
+		self.revision += 1;
+	}
+
+	/// Stops merging changes into the last step.
+	pub fn close(&mut self) {
+		if !self.is_open {
+			return;
+		}
+
+		self.is_open = false;
+
+		// Every change of the step cancelled out.
+		if self.steps.last().is_some_and(|s| s.changes.is_empty()) {
+			self.steps.pop();
+			self.position -= 1;
+			self.revision += 1;
+		}
+	}
+
+	fn start_step(&mut self, changes: Vec<Change>) {
+		let position = self.position;
+
+		// The steps which were saved are replaced.
+		for saved in [&mut self.saved_position, &mut self.saving_position] {
+			if saved.is_some_and(|v| v > position) {
+				*saved = None;
+			}
+		}
+
+		self.steps.truncate(self.position);
+		self.steps.push(Step { changes });
+
+		if self.steps.len() > MAX_STEPS {
+			self.steps.remove(0);
+
+			for saved in [&mut self.saved_position, &mut self.saving_position] {
+				*saved = saved.and_then(|v| v.checked_sub(1));
+			}
+		}
+
+		self.position = self.steps.len();
+	}
+
+
+	/// Returns false if there's nothing to undo.
+	pub fn undo(&mut self, pixels: &mut PixelBackground) -> bool {
+		self.close();
+
+		if !self.can_undo() {
+			return false;
+		}
+
+		self.position -= 1;
+
+		for change in self.steps[self.position].changes.iter().rev() {
+			change.inverse().apply(pixels);
+		}
+
+		self.revision += 1;
+
+		true
+	}
+
+	/// Returns false if there's nothing to redo.
+	pub fn redo(&mut self, pixels: &mut PixelBackground) -> bool {
+		self.close();
+
+		if !self.can_redo() {
+			return false;
+		}
+
+		for change in &self.steps[self.position].changes {
+			change.apply(pixels);
+		}
+
+		self.position += 1;
+		self.revision += 1;
+
+		true
+	}
+
+	/// Undoes or redoes until `position` steps are applied.
+	pub fn go_to(&mut self, position: usize, pixels: &mut PixelBackground) {
+		while self.position > position && self.undo(pixels) {}
+		while self.position < position && self.redo(pixels) {}
+	}
+}
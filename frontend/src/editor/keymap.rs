@@ -0,0 +1,372 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+
+use crate::{Result, statics, window};
+
+use super::KeyboardEvent;
+
+
+/// Key of the keymap in Local Storage. Suffixed with the user id.
+const KEYMAP_KEY: &str = "circuit-sim-keymap";
+
+
+/// Something the editor can do from a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Command {
+	Eraser,
+	Router,
+	NetSelect,
+	/// Paint with the connection color at the palette index.
+	Palette(u8),
+	/// Stop painting, erasing or selecting.
+	Cancel,
+	/// Cycle between the ways of painting wires.
+	NextWireMode,
+	/// Switch which run of the L-Shape wire comes first.
+	FlipBend,
+
+	ToggleRunning,
+	/// Only show the wave fronts while running.
+	TogglePropagation,
+	ZoomIn,
+	ZoomOut,
+	/// Zoom so everything on the Canvas is in view.
+	ZoomToFit,
+	ZoomToSelection,
+
+	Undo,
+	Redo,
+
+	Copy,
+	Cut,
+	Paste,
+	Delete,
+
+	RotateClockwise,
+	RotateCounterClockwise,
+	Mirror,
+
+	Save,
+	ShowShortcuts
+}
+
+impl Command {
+	/// Every command in the order they're shown in the cheat sheet.
+	pub fn all() -> Vec<Self> {
+		let mut commands = vec![Self::Eraser, Self::Router, Self::NetSelect];
+
+		commands.extend((0..9).map(Self::Palette));
+
+		commands.extend_from_slice(&[
+			Self::Cancel,
+			Self::NextWireMode,
+			Self::FlipBend,
+			Self::ToggleRunning,
+			Self::TogglePropagation,
+			Self::ZoomIn,
+			Self::ZoomOut,
+			Self::ZoomToFit,
+			Self::ZoomToSelection,
+			Self::Undo,
+			Self::Redo,
+			Self::Copy,
+			Self::Cut,
+			Self::Paste,
+			Self::Delete,
+			Self::RotateClockwise,
+			Self::RotateCounterClockwise,
+			Self::Mirror,
+			Self::Save,
+			Self::ShowShortcuts
+		]);
+
+		commands
+	}
+
+	pub fn display_name(self) -> String {
+		let name = match self {
+			Self::Eraser => "Eraser",
+			Self::Router => "Wire Router",
+			Self::NetSelect => "Select Net",
+			Self::Palette(index) => return format!("Connection Color {}", index + 1),
+			Self::Cancel => "Cancel / Deselect",
+			Self::NextWireMode => "Next Wire Mode",
+			Self::FlipBend => "Flip L-Shape Bend",
+			Self::ToggleRunning => "Run / Stop",
+			Self::TogglePropagation => "Toggle Propagation Animation",
+			Self::ZoomIn => "Zoom In",
+			Self::ZoomOut => "Zoom Out",
+			Self::ZoomToFit => "Zoom to Fit",
+			Self::ZoomToSelection => "Zoom to Selection",
+			Self::Undo => "Undo",
+			Self::Redo => "Redo",
+			Self::Copy => "Copy Selection",
+			Self::Cut => "Cut Selection",
+			Self::Paste => "Paste",
+			Self::Delete => "Delete Selection",
+			Self::RotateClockwise => "Rotate Clockwise",
+			Self::RotateCounterClockwise => "Rotate Counter Clockwise",
+			Self::Mirror => "Mirror",
+			Self::Save => "Save",
+			Self::ShowShortcuts => "Keyboard Shortcuts"
+		};
+
+		name.to_string()
+	}
+}
+
+
+/// A key along with the modifiers held down. Ctrl and Meta (Cmd) are treated the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+	/// Lowercase `KeyboardEvent.key`.
+	pub key: String,
+
+	pub ctrl: bool,
+	pub shift: bool,
+	pub alt: bool
+}
+
+impl Chord {
+	pub fn new(key: &str) -> Self {
+		Self {
+			key: key.to_lowercase(),
+			ctrl: false,
+			shift: false,
+			alt: false
+		}
+	}
+
+	#[must_use]
+	pub fn ctrl(mut self) -> Self {
+		self.ctrl = true;
+		self
+	}
+
+	#[must_use]
+	pub fn shift(mut self) -> Self {
+		self.shift = true;
+		self
+	}
+
+	/// Returns `None` for modifier keys pressed on their own.
+	pub fn from_event(event: &KeyboardEvent) -> Option<Self> {
+		if matches!(event.key.as_str(), "Control" | "Shift" | "Alt" | "Meta" | "AltGraph" | "CapsLock" | "Dead" | "Unidentified") {
+			return None;
+		}
+
+		// Shift is part of symbols like "?" or "+". Only letters and named keys keep it.
+		let is_symbol = event.key.chars().count() == 1 && !event.key.chars().any(char::is_alphabetic);
+
+		Some(Self {
+			key: event.key.to_lowercase(),
+			ctrl: event.is_ctrl_down || event.is_meta_down,
+			shift: event.is_shift_down && !is_symbol,
+			alt: event.is_alt_down
+		})
+	}
+
+	/// Parses the format from `Display`. Ex: "Ctrl+Shift+Z"
+	pub fn parse(value: &str) -> Option<Self> {
+		// The key itself can be "+".
+		let (modifiers, key) = match value.strip_suffix('+') {
+			Some(modifiers) => (modifiers, "+"),
+			None => value.rsplit_once('+').unwrap_or(("", value))
+		};
+
+		if key.is_empty() {
+			return None;
+		}
+
+		let mut chord = Self::new(if key == "Space" { " " } else { key });
+
+		for modifier in modifiers.split('+').filter(|v| !v.is_empty()) {
+			match modifier {
+				"Ctrl" => chord.ctrl = true,
+				"Shift" => chord.shift = true,
+				"Alt" => chord.alt = true,
+				_ => return None
+			}
+		}
+
+		Some(chord)
+	}
+}
+
+impl fmt::Display for Chord {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.ctrl {
+			f.write_str("Ctrl+")?;
+		}
+
+		if self.shift {
+			f.write_str("Shift+")?;
+		}
+
+		if self.alt {
+			f.write_str("Alt+")?;
+		}
+
+		match self.key.as_str() {
+			" " => f.write_str("Space"),
+			key if key.chars().count() == 1 => f.write_str(&key.to_uppercase()),
+
+			key => {
+				// Named keys are lowercased. Ex: "arrowup" -> "Arrowup"
+				let mut chars = key.chars();
+				let first = chars.next().map(|c| c.to_uppercase().to_string()).unwrap_or_default();
+
+				write!(f, "{first}{}", chars.as_str())
+			}
+		}
+	}
+}
+
+
+/// Chords bound to Commands. A Command can have more than one Chord.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+	pub bindings: Vec<(Chord, Command)>,
+
+	/// Local Storage key it was loaded from.
+	storage_key: String
+}
+
+impl Keymap {
+	/// Keymap of the current user. Falls back to the defaults if nothing was saved.
+	pub fn load() -> Self {
+		let storage_key = storage_key();
+
+		let saved: Option<Vec<(String, Command)>> = window().local_storage().ok().flatten()
+			.and_then(|s| s.get_item(&storage_key).ok().flatten())
+			.and_then(|v| serde_json::from_str(&v).ok());
+
+		let bindings = match saved {
+			Some(saved) => {
+				let mut bindings: Vec<(Chord, Command)> = saved.into_iter()
+					.filter_map(|(chord, command)| Some((Chord::parse(&chord)?, command)))
+					.collect();
+
+				// Commands added after it was saved get their default Chords if nothing else uses them.
+				let missing: Vec<(Chord, Command)> = default_bindings().into_iter()
+					.filter(|(_, command)| bindings.iter().all(|(_, v)| v != command))
+					.collect();
+
+				for (chord, command) in missing {
+					if bindings.iter().all(|(c, _)| *c != chord) {
+						bindings.push((chord, command));
+					}
+				}
+
+				bindings
+			}
+
+			None => default_bindings()
+		};
+
+		Self { bindings, storage_key }
+	}
+
+	pub fn save(&self) -> Result<()> {
+		let saved: Vec<(String, Command)> = self.bindings.iter()
+			.map(|(chord, command)| (chord.to_string(), *command))
+			.collect();
+
+		if let Some(storage) = window().local_storage()? {
+			storage.set_item(&self.storage_key, &serde_json::to_string(&saved)?)?;
+		}
+
+		Ok(())
+	}
+
+	/// Was it loaded for the user who is currently logged in.
+	pub fn is_current_user(&self) -> bool {
+		self.storage_key == storage_key()
+	}
+
+	pub fn command_for(&self, event: &KeyboardEvent) -> Option<Command> {
+		let chord = Chord::from_event(event)?;
+
+		self.bindings.iter()
+			.find(|(c, _)| *c == chord)
+			.map(|&(_, command)| command)
+	}
+
+	pub fn chords_for(&self, command: Command) -> Vec<&Chord> {
+		self.bindings.iter()
+			.filter(|&&(_, c)| c == command)
+			.map(|(chord, _)| chord)
+			.collect()
+	}
+
+	/// Replaces every Chord of the Command. The Chord is taken away from the Command which had it.
+	pub fn rebind(&mut self, command: Command, chord: Chord) {
+		self.bindings.retain(|(c, v)| *v != command && *c != chord);
+		self.bindings.push((chord, command));
+	}
+
+	pub fn reset(&mut self) {
+		self.bindings = default_bindings();
+	}
+}
+
+
+fn storage_key() -> String {
+	if statics::has_user_info() {
+		format!("{KEYMAP_KEY}-{}", statics::get_user_info().id)
+	} else {
+		KEYMAP_KEY.to_string()
+	}
+}
+
+fn default_bindings() -> Vec<(Chord, Command)> {
+	let mut bindings = vec![
+		(Chord::new("e"), Command::Eraser),
+		(Chord::new("w"), Command::Router),
+		(Chord::new("n"), Command::NetSelect),
+		(Chord::new("Escape"), Command::Cancel),
+		(Chord::new("l"), Command::NextWireMode),
+		(Chord::new("b"), Command::FlipBend),
+
+		(Chord::new(" "), Command::ToggleRunning),
+		(Chord::new("p"), Command::TogglePropagation),
+		(Chord::new("+"), Command::ZoomIn),
+		(Chord::new("="), Command::ZoomIn),
+		(Chord::new("-"), Command::ZoomOut),
+		(Chord::new("0"), Command::ZoomToFit),
+		(Chord::new("f"), Command::ZoomToSelection),
+
+		(Chord::new("z").ctrl(), Command::Undo),
+		(Chord::new("z").ctrl().shift(), Command::Redo),
+		(Chord::new("y").ctrl(), Command::Redo),
+
+		(Chord::new("c").ctrl(), Command::Copy),
+		(Chord::new("x").ctrl(), Command::Cut),
+		(Chord::new("v").ctrl(), Command::Paste),
+		(Chord::new("Delete"), Command::Delete),
+		(Chord::new("Backspace"), Command::Delete),
+
+		(Chord::new("r"), Command::RotateClockwise),
+		(Chord::new("r").shift(), Command::RotateCounterClockwise),
+		(Chord::new("m"), Command::Mirror),
+
+		(Chord::new("s").ctrl(), Command::Save),
+		(Chord::new("?"), Command::ShowShortcuts)
+	];
+
+	for index in 0..9 {
+		bindings.push((Chord::new(&(index + 1).to_string()), Command::Palette(index)));
+	}
+
+	bindings
+}
+
+
+/// Is the key being typed into a text field.
+pub fn is_typing(event: &KeyboardEvent) -> bool {
+	event.event.target()
+		.and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+		.is_some_and(|e| matches!(e.tag_name().as_str(), "INPUT" | "TEXTAREA"))
+}
@@ -1,6 +1,6 @@
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
@@ -16,17 +16,31 @@ use web_sys::{
 use crate::{
 	window,
 	Result,
-	canvas::Canvas,
+	canvas::{Canvas, StaticLayer},
 	error::EditingError,
 	statics::{
 		get_editor_state,
 		get_editor_state_mut,
-		is_editor_running
+		get_keymap,
+		is_editor_running,
+		request_render,
+		set_editor_running,
+		take_render_request
 	},
-	ui::MainUi,
+	ui::{HoverInspector, MainUi, shortcuts_popup},
+	config::save_editor,
 	objects::ObjectUpdateEvent
 };
 
+pub mod documents;
+pub mod drc;
+pub mod drawing;
+pub mod history;
+pub mod net;
+pub mod route;
+pub mod keymap;
+pub mod selection;
+pub mod signals;
 pub mod node;
 pub mod state;
 pub mod event;
@@ -34,6 +48,7 @@ pub mod util;
 pub mod ui;
 
 pub use node::*;
+pub use keymap::Command;
 pub use event::{MouseEvent, WheelEvent, KeyboardEvent, MouseButton};
 pub use util::{Cursor, ViewOptions};
 pub use state::{EditorState, CanvasState};
@@ -88,6 +103,18 @@ impl Editor {
 		// Mouse Up Event
 		create_event!("mouseup", MouseUp, HtmlMouseEvent, writable.canvas.element, self.clone());
 
+		{ // Mouse Leave. Hides the Hover Inspector.
+			let inner = self.clone();
+
+			let closure = Closure::wrap(Box::new(move |_: HtmlMouseEvent| {
+				if let Ok(editor) = inner.read() {
+					editor.inspector.hide();
+				}
+			}) as Box<dyn FnMut(_)>);
+			writable.canvas.element.add_event_listener_with_callback("mouseleave", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
 		// Mouse Scroll Event
 		create_event!("wheel", Scroll, HtmlWheelEvent, writable.canvas.element, self.clone());
 
@@ -114,8 +141,10 @@ impl Editor {
 				log!("Update Error: {:?}", e);
 			}
 
-			if let Err(e) = mutable.render() {
-				log!("Render Error: {:?}", e);
+			if mutable.needs_render() {
+				if let Err(e) = mutable.render() {
+					log!("Render Error: {:?}", e);
+				}
 			}
 
 			request_animation_frame(f.borrow().as_ref().unwrap());
@@ -133,13 +162,23 @@ pub struct InnerEditor {
 	pub global_update_every: f64,
 	pub global_last_update: f64,
 
-	pub canvas: Canvas
+	pub canvas: Canvas,
+	/// Background and cells. Kept between frames.
+	pub layer: StaticLayer,
+	/// Tooltip of the hovered cell.
+	pub inspector: HoverInspector,
+
+	/// History revision the last frame was drawn at.
+	rendered_revision: Cell<Option<usize>>
 }
 
 impl InnerEditor {
 	pub fn new(width: usize, height: usize) -> Self {
 		Self {
 			canvas: Canvas::new(),
+			layer: StaticLayer::new(),
+			inspector: HoverInspector::new(),
+			rendered_revision: Cell::new(None),
 			view_opts: ViewOptions::new(width, height),
 			main_ui: None,
 
@@ -151,6 +190,7 @@ impl InnerEditor {
 
 	pub fn init(&mut self, parent: &HtmlDivElement) -> Result<()> {
 		parent.append_with_node_1(self.canvas.as_ref())?;
+		parent.append_with_node_1(self.inspector.as_ref())?;
 
 		self.resize(self.view_opts.canvas_width(), self.view_opts.canvas_height());
 
@@ -165,11 +205,28 @@ impl InnerEditor {
 
 		self.view_opts.set_width(width);
 		self.view_opts.set_height(height);
+
+		request_render();
+	}
+
+	/// Frames are skipped until something which is drawn changed.
+	pub fn needs_render(&self) -> bool {
+		let is_requested = take_render_request();
+
+		match get_editor_state().get_canvas_state() {
+			Some(canvas) => is_requested ||
+				self.layer.is_stale(&canvas.pixels, &self.view_opts) ||
+				self.rendered_revision.get() != Some(canvas.history.revision()),
+
+			None => is_requested
+		}
 	}
 
 	pub fn render(&self) -> Result<()> {
 		let ctx = &self.canvas;
 
+		self.rendered_revision.set(get_editor_state().get_canvas_state().map(|canvas| canvas.history.revision()));
+
 		ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
 		ctx.clear_rect(0.0, 0.0, self.view_opts.width_f64(), self.view_opts.height_f64());
 		ctx.set_font("12px Verdana");
@@ -192,7 +249,7 @@ impl InnerEditor {
 							let state = get_editor_state_mut();
 
 							if let Some(canvas_state) = state.get_canvas_state_mut() {
-								for o in &mut canvas_state.pixels.objects {
+								for o in canvas_state.pixels.objects_mut() {
 									if let Some(ticker) = o.as_tickable_mut() {
 										crate::statics::add_to_ticking(ticker.tick(ObjectUpdateEvent::GlobalTick)?);
 									}
@@ -201,7 +258,18 @@ impl InnerEditor {
 
 							crate::statics::tick()?;
 
+							if let Some(canvas_state) = get_editor_state_mut().get_canvas_state_mut() {
+								canvas_state.signals.finish_tick(&canvas_state.pixels);
+							}
+
 							self.global_last_update = curr_time;
+
+							request_render();
+
+							// Values changed under the cursor.
+							if self.inspector.is_shown() {
+								self.inspector.update(&self.view_opts, &self.canvas.element)?;
+							}
 						}
 					}
 				}
@@ -226,15 +294,15 @@ impl InnerEditor {
 
 					// Canvas Dragging
 					if let Some((start_x, start_y)) = self.view_opts.get_dragging_canvas_start_pos() {
-						let new_x = (start_x - mouse.fixed_x).max(0.0);
-						let new_y = (start_y - mouse.fixed_y).max(0.0);
+						let new_x = start_x - mouse.fixed_x;
+						let new_y = start_y - mouse.fixed_y;
 
 						self.view_opts.set_drag_pos(new_x, new_y);
 					}
 				}
 
 				EditorEvent::Scroll(mouse) => {
-					self.view_opts.update_zoom(mouse.direction);
+					self.view_opts.zoom_at(mouse.direction, mouse.fixed_x, mouse.fixed_y);
 				}
 
 				EditorEvent::KeyDown(keyboard) | EditorEvent::KeyUp(keyboard) => {
@@ -245,10 +313,27 @@ impl InnerEditor {
 
 				_ => ()
 			}
+
+			if !matches!(event, EditorEvent::GlobalTick) {
+				request_render();
+			}
 		}
 
 		get_editor_state_mut().update(self, &event)?;
 
+		if let EditorEvent::MouseMove(_) = &event {
+			self.inspector.update(&self.view_opts, &self.canvas.element)?;
+		}
+
+		// Keyboard Shortcuts
+		if let EditorEvent::KeyDown(keyboard) = &event {
+			if let Some(command) = get_keymap().command_for(keyboard).filter(|_| !keymap::is_typing(keyboard)) {
+				keyboard.event.prevent_default();
+
+				self.run_command(command)?;
+			}
+		}
+
 		// Artificial MouseClick since "click" event only listens to left clicks.
 		if let EditorEvent::MouseUp(mouse) = event {
 			self.update(EditorEvent::MouseClick(mouse), editor_clone)?;
@@ -256,6 +341,49 @@ impl InnerEditor {
 
 		Ok(())
 	}
+
+	pub fn run_command(&mut self, command: Command) -> Result<()> {
+		match command {
+			// Nothing to run while a Canvas is still loading.
+			Command::ToggleRunning => if get_editor_state().get_canvas_state().is_some() {
+				set_editor_running(!is_editor_running());
+				toggled_edit_mode()?;
+			}
+
+			Command::TogglePropagation => {
+				if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+					canvas.signals.is_animated = !canvas.signals.is_animated;
+				}
+			}
+
+			Command::ZoomIn => self.view_opts.zoom_at_center(1),
+			Command::ZoomOut => self.view_opts.zoom_at_center(-1),
+
+			Command::ZoomToFit | Command::ZoomToSelection => {
+				let bounds = get_editor_state().get_canvas_state().and_then(|canvas| {
+					if command == Command::ZoomToFit {
+						canvas.content_bounds()
+					} else {
+						canvas.selection_bounds()
+					}
+				});
+
+				if let Some((min, max)) = bounds {
+					self.view_opts.zoom_to_cells(min, max);
+				}
+			}
+
+			Command::Save => save_editor(|_| {
+				log!("saved");
+			})?,
+
+			Command::ShowShortcuts => shortcuts_popup()?,
+
+			command => get_editor_state_mut().update(self, &EditorEvent::Command(command))?
+		}
+
+		Ok(())
+	}
 }
 
 
@@ -269,10 +397,10 @@ pub fn request_animation_frame(f: &Closure<dyn FnMut()>) {
 
 
 pub fn toggled_edit_mode() -> Result<()> {
-	let editor = get_editor_state_mut();
-	let canvas = editor.get_canvas_state_mut().unwrap();
-
-	canvas.pixels.reset_all()?;
+	if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+		canvas.pixels.reset_all()?;
+		canvas.signals.clear();
+	}
 
 	Ok(())
 }
@@ -294,6 +422,9 @@ pub enum EditorEvent {
 	KeyDown(KeyboardEvent),
 	KeyUp(KeyboardEvent),
 
+	/// Sent after the `KeyDown` of a keyboard shortcut.
+	Command(Command),
+
 	Scroll(WheelEvent),
 
 	GlobalTick
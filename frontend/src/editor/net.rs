@@ -0,0 +1,78 @@
+use std::collections::{HashSet, VecDeque};
+
+use circuit_sim_common::{CellPos, config::NodeJson, netlist::{Netlist, NodeRef}};
+
+use crate::canvas::PixelBackground;
+
+
+/// Every wire cell connected to the cell through wires of the same color. Empty if the cell isn't a wire.
+pub fn flood_net(pixels: &PixelBackground, cell: CellPos) -> Vec<CellPos> {
+	let Some(index) = wire_index(pixels, cell) else { return Vec::new() };
+
+	let mut visited = HashSet::new();
+	let mut queue = VecDeque::new();
+
+	visited.insert(cell);
+	queue.push_back(cell);
+
+	while let Some(current) = queue.pop_front() {
+		for (next, _) in PixelBackground::get_surrounding_cells(current) {
+			if wire_index(pixels, next) == Some(index) && visited.insert(next) {
+				queue.push_back(next);
+			}
+		}
+	}
+
+	let mut cells: Vec<CellPos> = visited.into_iter().collect();
+	cells.sort_unstable();
+
+	cells
+}
+
+/// Palette index of the net. `None` if it's empty.
+pub fn net_index(pixels: &PixelBackground, net: &[CellPos]) -> Option<usize> {
+	net.first().and_then(|&c| wire_index(pixels, c))
+}
+
+/// Wire cells of another net which would connect to the net if it was recolored to `index`.
+pub fn merging_cells(pixels: &PixelBackground, net: &[CellPos], index: usize) -> Vec<CellPos> {
+	let cells: HashSet<CellPos> = net.iter().copied().collect();
+
+	let mut merging: Vec<CellPos> = net.iter()
+		.flat_map(|&c| PixelBackground::get_surrounding_cells(c))
+		.map(|(next, _)| next)
+		.filter(|next| !cells.contains(next) && wire_index(pixels, *next) == Some(index))
+		.collect();
+
+	merging.sort_unstable();
+	merging.dedup();
+
+	merging
+}
+
+
+/// Nodes of every Object. Indexed the same as `PixelBackground::objects`.
+pub fn object_nodes(pixels: &PixelBackground) -> Vec<Vec<NodeJson>> {
+	pixels.objects().iter()
+		.map(|o| o.get_object_state().nodes.iter().map(Into::into).collect())
+		.collect()
+}
+
+/// Connectivity of the Canvas. `object_nodes` has to come from `object_nodes`.
+pub fn netlist(pixels: &PixelBackground, object_nodes: &[Vec<NodeJson>]) -> Netlist {
+	let wires: Vec<(CellPos, usize)> = pixels.cells.iter()
+		.filter_map(|(&cell, pixel)| pixel.type_of.get_wire_palette_index().map(|index| (cell, index)))
+		.collect();
+
+	let nodes: Vec<(NodeRef, &NodeJson)> = object_nodes.iter()
+		.enumerate()
+		.flat_map(|(object, nodes)| nodes.iter().enumerate().map(move |(i, n)| ((object, i), n)))
+		.collect();
+
+	Netlist::from_parts(wires, &nodes, |a, b| pixels.palette[a].0 == pixels.palette[b].0)
+}
+
+
+fn wire_index(pixels: &PixelBackground, cell: CellPos) -> Option<usize> {
+	pixels.cells.get(&cell).and_then(|p| p.type_of.get_wire_palette_index())
+}
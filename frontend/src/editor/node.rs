@@ -12,6 +12,8 @@ pub struct Node {
 
 	pub accepts: NodeValueTypes,
 
+	pub label: Option<String>,
+
 	pub is_disabled: bool
 }
 
@@ -22,6 +24,7 @@ impl Node {
 			direction,
 			accepts,
 
+			label: None,
 			is_disabled: false,
 			cell_pos: (0, 0)
 		}
@@ -48,7 +51,7 @@ impl Into<NodeJson> for &Node {
 			direction: self.direction,
 			accepts: self.accepts,
 			is_disabled: self.is_disabled,
-			label: None
+			label: self.label.clone()
 		}
 	}
 }
@@ -60,6 +63,7 @@ impl From<NodeJson> for Node {
 			side: value.side,
 			direction: value.direction,
 			accepts: value.accepts,
+			label: value.label,
 			is_disabled: value.is_disabled
 		}
 	}
@@ -0,0 +1,72 @@
+use circuit_sim_common::{CellPos, NodeDirection, route::{self, RouteBounds}};
+
+use crate::{canvas::PixelBackground, editor::Node};
+
+
+/// Cells around the two Nodes which the router is allowed to search in.
+const ROUTE_MARGIN: i32 = 20;
+
+
+/// Node whose pixel is at the cell.
+pub fn find_node(pixels: &PixelBackground, cell: CellPos) -> Option<&Node> {
+	pixels.objects().iter()
+		.flat_map(|o| o.get_object_state().nodes.iter())
+		.find(|n| n.cell_pos == cell)
+}
+
+
+/// Shortest Manhattan path of wire cells from an output Node to an input Node.
+///
+/// The path only goes through free cells and never touches another Node or a wire of the same color
+/// which would connect it to something else. Returns why it failed otherwise.
+pub fn route_between(pixels: &PixelBackground, from: &Node, to: &Node, palette_index: usize) -> Result<Vec<CellPos>, String> {
+	if from.direction != NodeDirection::Output || to.direction != NodeDirection::Input {
+		return Err(String::from("Wires are routed from an output Node to an input Node."));
+	}
+
+	let start = from.side.as_facing_cell_pos(from.cell_pos);
+	let goal = to.side.as_facing_cell_pos(to.cell_pos);
+
+	// Empty or already connected with the same color.
+	let is_free = |cell: CellPos| pixels.cells.get(&cell).is_none_or(|p| p.type_of.get_wire_palette_index() == Some(palette_index));
+
+	if !is_free(start) {
+		return Err(String::from("The output Node is blocked."));
+	}
+
+	if !is_free(goal) {
+		return Err(String::from("The input Node is blocked."));
+	}
+
+	let is_passable = |cell: CellPos| {
+		if cell == goal {
+			return true;
+		}
+
+		if pixels.cells.contains_key(&cell) {
+			return false;
+		}
+
+		PixelBackground::get_surrounding_cells(cell).into_iter().all(|(next, _)| {
+			match pixels.cells.get(&next) {
+				Some(p) if p.type_of.is_node() => next == from.cell_pos || next == to.cell_pos,
+				Some(p) if p.type_of.get_wire_palette_index() == Some(palette_index) => next == start || next == goal,
+				_ => true
+			}
+		})
+	};
+
+	let bounds = RouteBounds::new(
+		(
+			start.0.min(goal.0) - ROUTE_MARGIN,
+			start.1.min(goal.1) - ROUTE_MARGIN
+		),
+		(
+			start.0.max(goal.0) + ROUTE_MARGIN,
+			start.1.max(goal.1) + ROUTE_MARGIN
+		)
+	);
+
+	route::find_path(&[start], goal, bounds, is_passable)
+		.ok_or_else(|| String::from("No route was found between the Nodes."))
+}
@@ -0,0 +1,232 @@
+use std::collections::{HashMap, HashSet};
+
+use circuit_sim_common::{CellPos, offset_cell_pos, config::{CanvasStateJson, ObjectsJson}};
+
+use crate::{
+	canvas::{PixelBackground, PixelMap},
+	config::{object_to_json, palette_to_json},
+	editor::{Node, history::{Change, apply_change, set_wire_cell}},
+	ids::ObjectId,
+	objects::Renderable
+};
+
+
+/// Key of the clipboard in Local Storage. Shared by every tab.
+pub const CLIPBOARD_KEY: &str = "circuit-sim-clipboard";
+
+
+/// Objects and Wires captured inside of a rectangle.
+#[derive(Debug, Clone)]
+pub struct Selection {
+	/// Top left cell.
+	pub min: CellPos,
+	/// Bottom right cell. Inclusive.
+	pub max: CellPos,
+
+	pub objects: Vec<ObjectId>,
+	pub wires: Vec<CellPos>,
+
+	/// Cells the selection is dragged by. Applied once it's dropped.
+	pub offset: CellPos
+}
+
+impl Selection {
+	/// Captures the Objects which are fully inside of the rectangle and the Wires inside of it.
+	pub fn from_corners(pixels: &PixelBackground, a: CellPos, b: CellPos) -> Self {
+		let min = (a.0.min(b.0), a.1.min(b.1));
+		let max = (a.0.max(b.0), a.1.max(b.1));
+
+		let is_inside = |(x, y): CellPos| x >= min.0 && y >= min.1 && x <= max.0 && y <= max.1;
+
+		let objects = pixels.objects_between(min, max)
+			.filter(|o| o.pixel_map().pixel_positions(o.get_cell_pos()).into_iter().all(is_inside))
+			.map(Renderable::get_id)
+			.collect();
+
+		let mut wires: Vec<CellPos> = pixels.cells.iter_between(min, max)
+			.filter(|(&c, p)| p.type_of.is_wire() && is_inside(c))
+			.map(|(&c, _)| c)
+			.collect();
+
+		wires.sort_unstable();
+
+		Self { min, max, objects, wires, offset: (0, 0) }
+	}
+
+	/// Selection which fits around the Objects and Wires.
+	pub fn from_parts(pixels: &PixelBackground, objects: Vec<ObjectId>, wires: Vec<CellPos>) -> Self {
+		let mut selection = Self { min: (0, 0), max: (0, 0), objects, wires, offset: (0, 0) };
+
+		let cells = selection.cells(pixels);
+
+		selection.min = (
+			cells.iter().map(|c| c.0).min().unwrap_or_default(),
+			cells.iter().map(|c| c.1).min().unwrap_or_default()
+		);
+
+		selection.max = (
+			cells.iter().map(|c| c.0).max().unwrap_or_default(),
+			cells.iter().map(|c| c.1).max().unwrap_or_default()
+		);
+
+		selection
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.objects.is_empty() && self.wires.is_empty()
+	}
+
+	/// Is the cell inside of the rectangle.
+	pub fn contains(&self, (x, y): CellPos) -> bool {
+		x >= self.min.0 && y >= self.min.1 && x <= self.max.0 && y <= self.max.1
+	}
+
+	/// Every cell of the selected Wires and Objects.
+	pub fn cells(&self, pixels: &PixelBackground) -> Vec<CellPos> {
+		let mut cells = self.wires.clone();
+
+		for object in self.objects.iter().filter_map(|&id| pixels.get_object_by_id(id)) {
+			cells.append(&mut object.pixel_map().pixel_positions(object.get_cell_pos()));
+		}
+
+		cells
+	}
+
+
+	/// Can every selected cell be moved by `offset` without going off the Canvas or overlapping an Object which isn't selected.
+	pub fn can_move_by(&self, pixels: &PixelBackground, offset: CellPos) -> bool {
+		let occupied: HashSet<CellPos> = pixels.objects().iter()
+			.filter(|o| !self.objects.contains(&o.get_id()))
+			.flat_map(|o| o.pixel_map().pixel_positions(o.get_cell_pos()))
+			.collect();
+
+		self.cells(pixels)
+			.into_iter()
+			.all(|cell| offset_cell_pos(cell, offset).is_some_and(|c| !occupied.contains(&c)))
+	}
+
+	/// Moves everything by `offset`. Wires which aren't selected are removed from under the moved Objects.
+	///
+	/// Returns the changes which were made. Nothing is moved if `can_move_by` fails.
+	pub fn move_by(&mut self, pixels: &mut PixelBackground, offset: CellPos) -> Vec<Change> {
+		let mut changes = Vec::new();
+
+		if offset == (0, 0) || !self.can_move_by(pixels, offset) {
+			return changes;
+		}
+
+		let wires: Vec<(CellPos, Option<usize>)> = self.wires.iter()
+			.map(|&c| (c, pixels.cells.get(&c).and_then(|p| p.type_of.get_wire_palette_index())))
+			.collect();
+
+		for &(cell, _) in &wires {
+			set_wire_cell(pixels, &mut changes, cell, None);
+		}
+
+		for &id in &self.objects {
+			let Some(object) = pixels.get_object_by_id(id) else { continue };
+
+			let from = object.get_cell_pos();
+			let to = offset_cell_pos(from, offset).unwrap_or(from);
+
+			let change = Change::MoveObject { id, type_of: object.get_object_state().type_of, from, to };
+
+			for cell in object.pixel_map().pixel_positions(to) {
+				set_wire_cell(pixels, &mut changes, cell, None);
+			}
+
+			apply_change(pixels, &mut changes, change);
+		}
+
+		self.wires.clear();
+
+		for (cell, index) in wires {
+			if let Some(cell) = offset_cell_pos(cell, offset) {
+				set_wire_cell(pixels, &mut changes, cell, index);
+				self.wires.push(cell);
+			}
+		}
+
+		self.min = offset_cell_pos(self.min, offset).unwrap_or(self.min);
+		self.max = offset_cell_pos(self.max, offset).unwrap_or(self.max);
+		self.offset = (0, 0);
+
+		changes
+	}
+
+	/// Deletes everything which is selected. Returns the changes which were made.
+	pub fn delete(&self, pixels: &mut PixelBackground) -> Vec<Change> {
+		let mut changes = Vec::new();
+
+		for &cell in &self.wires {
+			set_wire_cell(pixels, &mut changes, cell, None);
+		}
+
+		for &id in &self.objects {
+			if let Some(object) = pixels.get_object_by_id(id) {
+				let change = Change::DeleteObject(object_to_json(object));
+
+				apply_change(pixels, &mut changes, change);
+			}
+		}
+
+		changes
+	}
+
+	/// Clipboard fragment of the selection. Positions are relative to the top left of the rectangle.
+	pub fn to_json(&self, pixels: &PixelBackground) -> CanvasStateJson {
+		let offset = (-self.min.0, -self.min.1);
+
+		let mut wires: HashMap<usize, Vec<CellPos>> = HashMap::new();
+
+		for &cell in &self.wires {
+			if let (Some(index), Some(cell)) = (pixels.cells.get(&cell).and_then(|p| p.type_of.get_wire_palette_index()), offset_cell_pos(cell, offset)) {
+				wires.entry(index).or_default().push(cell);
+			}
+		}
+
+		let objects = self.objects.iter()
+			.filter_map(|&id| pixels.get_object_by_id(id))
+			.map(|object| {
+				let mut json = object_to_json(object);
+				json.pos = offset_cell_pos(json.pos, offset).unwrap_or_default();
+
+				ObjectsJson::Normal(json)
+			})
+			.collect();
+
+		CanvasStateJson {
+			objects,
+			color_palette: palette_to_json(&pixels.palette),
+			pixels: wires,
+			text_objects: Vec::new(),
+			bookmarks: Vec::new()
+		}
+	}
+}
+
+
+/// Can a clipboard fragment be pasted at `pos` without overlapping an Object.
+pub fn can_insert_json(pixels: &PixelBackground, json: &CanvasStateJson, (x, y): CellPos) -> bool {
+	let occupied: HashSet<CellPos> = pixels.objects().iter()
+		.flat_map(|o| o.pixel_map().pixel_positions(o.get_cell_pos()))
+		.collect();
+
+	let wires_free = json.pixels.values()
+		.flatten()
+		.all(|&(w_x, w_y)| !occupied.contains(&(w_x + x, w_y + y)));
+
+	let objects_free = json.objects.iter()
+		.all(|object| {
+			let ObjectsJson::Normal(object) = object else { return false };
+
+			let nodes: Vec<Node> = object.nodes.iter().cloned().map(Into::into).collect();
+
+			PixelMap::new_with_nodes(object.dim, &nodes)
+				.pixel_positions((object.pos.0 + x, object.pos.1 + y))
+				.iter()
+				.all(|c| !occupied.contains(c))
+		});
+
+	wires_free && objects_free
+}
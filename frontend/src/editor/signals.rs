@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use web_sys::CanvasRenderingContext2d;
+
+use circuit_sim_common::{CellPos, object::NodeValue};
+
+use crate::{
+	Result,
+	canvas::{PixelBackground, PixelColor, PixelType},
+	editor::{ViewOptions, net::flood_net}
+};
+
+
+/// Color of a Node which is active.
+const NODE_ON: PixelColor = PixelColor(66, 251, 85);
+/// Color of a Node which isn't.
+const NODE_OFF: PixelColor = PixelColor(90, 90, 90);
+
+
+/// Cells which received an active value during a tick.
+#[derive(Default)]
+struct Received {
+	wires: HashSet<CellPos>,
+	inputs: HashSet<CellPos>
+}
+
+
+/// Where the values went while running. Shown on top of the cells until the next tick finishes.
+#[derive(Default)]
+pub struct Signals {
+	/// Only show the wave fronts instead of every net a value went through.
+	pub is_animated: bool,
+
+	/// Filled while the tick is running.
+	current: Received,
+	/// Shown while the next tick is running.
+	lit: Received,
+
+	/// Last value every input Node received. Kept until it stops running.
+	received: HashMap<CellPos, NodeValue>
+}
+
+impl Signals {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// A value was sent into the wire cell.
+	pub fn send(&mut self, cell: CellPos, value: NodeValue) {
+		if value.is_active() {
+			self.current.wires.insert(cell);
+		}
+	}
+
+	/// A value was received by the input Node at the cell.
+	pub fn receive(&mut self, cell: CellPos, value: NodeValue) {
+		self.received.insert(cell, value);
+
+		if value.is_active() {
+			self.current.inputs.insert(cell);
+		}
+	}
+
+	/// Shows what was received during the tick.
+	pub fn finish_tick(&mut self, pixels: &PixelBackground) {
+		let received = mem::take(&mut self.current);
+
+		if self.is_animated {
+			self.lit = received;
+			return;
+		}
+
+		let mut wires = HashSet::new();
+
+		for cell in received.wires {
+			if !wires.contains(&cell) {
+				wires.extend(flood_net(pixels, cell));
+			}
+		}
+
+		self.lit = Received {
+			wires,
+			inputs: received.inputs
+		};
+	}
+
+	/// Last value the input Node at the cell received while running.
+	pub fn received_value(&self, cell: CellPos) -> Option<NodeValue> {
+		self.received.get(&cell).copied()
+	}
+
+	pub fn clear(&mut self) {
+		self.current = Received::default();
+		self.lit = Received::default();
+		self.received.clear();
+	}
+
+
+	/// Draws the active wires in their second palette color with a glow and the value of every Node.
+	pub fn render(&self, pixels: &PixelBackground, view: &ViewOptions, ctx: &CanvasRenderingContext2d) -> Result<()> {
+		let pixel_size = view.pixel_size as f64;
+
+		let (x, y, width, height) = view.cells_in_view();
+		let (min, max) = ((x, y), (x + width as i32, y + height as i32));
+
+		let is_inside = |&(x, y): &CellPos| x >= min.0 && y >= min.1 && x <= max.0 && y <= max.1;
+
+		ctx.set_shadow_blur(pixel_size / 2.0);
+
+		for &(x, y) in self.lit.wires.iter().filter(|c| is_inside(c)) {
+			let Some(pixel) = pixels.cells.get(&(x, y)) else { continue };
+			let PixelType::Wire { index, .. } = pixel.type_of else { continue };
+
+			let color = pixels.palette[index].1;
+
+			ctx.set_shadow_color(&color.get_string_color());
+			pixel.display.render(x, y, pixel_size, color, ctx);
+		}
+
+		ctx.set_shadow_blur(0.0);
+
+		// Nodes
+		let size = pixel_size / 3.0;
+
+		for object in pixels.objects_between(min, max) {
+			for node in &object.get_object_state().nodes {
+				let is_active = if node.direction.is_output() {
+					object.current_value().is_active()
+				} else {
+					self.lit.inputs.contains(&node.cell_pos)
+				};
+
+				let color = if is_active { NODE_ON } else { NODE_OFF };
+
+				ctx.set_fill_style_str(&color.get_string_color());
+
+				ctx.fill_rect(
+					node.cell_pos.0 as f64 * pixel_size + (pixel_size - size) / 2.0,
+					node.cell_pos.1 as f64 * pixel_size + (pixel_size - size) / 2.0,
+					size,
+					size
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
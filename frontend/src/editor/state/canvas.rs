@@ -1,29 +1,42 @@
 use std::any::Any;
 
-use circuit_sim_common::config::StateInfo;
+use circuit_sim_common::{CellPos, Dimensions, NodeDirection, NodeValueTypes, config::{ObjectNormalJson, StateInfo, ViewBookmarkJson}, offset_cell_pos};
 use js_sys::Date;
 use wasm_bindgen::prelude::*;
+use web_sys::CanvasRenderingContext2d;
 
 
 
 use crate::{
+	NotificationType,
 	Result,
+	window,
 	canvas::{
 		PIXEL_TOOLS,
 		PixelDisplay,
 		PixelColor,
 		PixelBackground
 	},
+	config::{create_object_from_normal_json, insert_state_json_at, object_to_json},
 	editor::{
 		InnerEditor,
-		EditorEvent
+		EditorEvent,
+		Node,
+		drawing::WireMode,
+		history::{Change, History, apply_change, set_wire_cell},
+		net,
+		route,
+		keymap::Command,
+		signals::Signals,
+		selection::{CLIPBOARD_KEY, Selection, can_insert_json}
 	},
-	ids::ObjectId,
-	objects::ObjectUpdateEvent,
+	ids::{DocumentId, ObjectId},
+	objects::{Editable, ObjectState, ObjectUpdateEvent, PropertyValue, Renderable},
 	statics::{
 		self,
 		is_editor_running
-	}
+	},
+	ui::net_popup
 };
 
 use super::EditorState;
@@ -35,14 +48,38 @@ pub enum CanvasEvent {
 	SelectedObject(ObjectId),
 	MovingObject(ObjectId),
 
+	/// Dragging the resize handle of the selected Object. Holds the Object from before.
+	ResizingObject(ObjectId, ObjectNormalJson),
+	/// Dragging a Node of the selected Object. (Object, Node index, Object from before)
+	MovingNode(ObjectId, usize, ObjectNormalJson),
+
+	/// Dragging out a selection rectangle from the cell.
+	Selecting(CellPos),
+	Selection(Selection),
+
 	Painting(PixelColor),
-	Tooling(CanvasTool)
+	/// Dragging out a shape of wires from the cell. Placed once it's released.
+	Drawing(PixelColor, CellPos),
+	Tooling(CanvasTool),
+
+	/// Wire cells of the net picked with `CanvasTool::NetSelect`.
+	Net(Vec<CellPos>),
+
+	/// Routing a wire of the color. Holds the output Node cell once it's clicked.
+	Routing {
+		color: PixelColor,
+		from: Option<CellPos>
+	}
 }
 
 
 #[derive(Debug, Clone, Copy)]
 pub enum CanvasTool {
-	Eraser
+	Eraser,
+	/// Selecting it starts `CanvasEvent::Routing`.
+	Router,
+	/// Picks a whole net of wires. See `CanvasEvent::Net`.
+	NetSelect
 }
 
 
@@ -52,7 +89,31 @@ pub struct CanvasState {
 	pub info: StateInfo,
 
 	pub pixels: PixelBackground,
-	pub event: Option<CanvasEvent>
+	pub event: Option<CanvasEvent>,
+	/// How dragging while painting lays down wires.
+	pub wire_mode: WireMode,
+
+	/// Cells of the selected Design-Rule Check finding.
+	pub highlighted: Vec<CellPos>,
+	/// Wire cells of the critical path from the timing report.
+	pub critical_path: Vec<CellPos>,
+	/// History revision the critical path was found at. It's not drawn once the Canvas is edited.
+	pub critical_path_revision: usize,
+	/// Any wire cell of the highlighted net. The net is found again on every render so it follows edits.
+	pub highlighted_net: Option<CellPos>,
+
+	/// Saved views. Stored with the Canvas.
+	pub bookmarks: Vec<ViewBookmarkJson>,
+
+	/// Values sent while running.
+	pub signals: Signals,
+
+	pub history: History,
+	/// Finds it again once a save finishes. It could've been moved into a different tab by then.
+	pub document_id: DocumentId,
+
+	/// Where the moving Object was picked up from. `None` if it's a new Object.
+	pub moving_from: Option<CellPos>
 }
 
 impl CanvasState {
@@ -60,15 +121,347 @@ impl CanvasState {
 		Self {
 			info: StateInfo::from_user_id(1, Date::now() as i64),
 			pixels: PixelBackground::new(),
-			event: None
+			event: None,
+			wire_mode: WireMode::default(),
+			highlighted: Vec::new(),
+			critical_path: Vec::new(),
+			critical_path_revision: 0,
+			highlighted_net: None,
+			bookmarks: Vec::new(),
+			signals: Signals::new(),
+			history: History::new(),
+			document_id: DocumentId::gen_id(),
+			moving_from: None
+		}
+	}
+
+	/// Was it changed since it was loaded or saved.
+	pub fn is_edited(&self) -> bool {
+		!self.history.is_saved()
+	}
+
+	/// Corners around every Wire and Object. `None` if the Canvas is empty.
+	pub fn content_bounds(&self) -> Option<(CellPos, CellPos)> {
+		cell_bounds(
+			self.pixels.cells.keys()
+				.copied()
+				.chain(self.pixels.objects().iter().flat_map(|o| o.pixel_map().pixel_positions(o.get_cell_pos())))
+		)
+	}
+
+	/// Corners around the selection, selected Object or selected net.
+	pub fn selection_bounds(&self) -> Option<(CellPos, CellPos)> {
+		match self.event.as_ref()? {
+			CanvasEvent::Selection(selection) => Some((
+				offset_cell_pos(selection.min, selection.offset)?,
+				offset_cell_pos(selection.max, selection.offset)?
+			)),
+
+			CanvasEvent::SelectedObject(id) |
+			CanvasEvent::MovingObject(id) |
+			CanvasEvent::ResizingObject(id, _) |
+			CanvasEvent::MovingNode(id, _, _) => {
+				let object = self.pixels.get_object_by_id(*id)?;
+
+				cell_bounds(object.pixel_map().pixel_positions(object.get_cell_pos()))
+			}
+
+			CanvasEvent::Net(net) => cell_bounds(net.iter().copied()),
+
+			_ => None
+		}
+	}
+
+	/// Returns false if there's nothing to undo or an Object is being moved.
+	pub fn undo(&mut self) -> bool {
+		self.prepare_history() && self.history.undo(&mut self.pixels)
+	}
+
+	/// Returns false if there's nothing to redo or an Object is being moved.
+	pub fn redo(&mut self) -> bool {
+		self.prepare_history() && self.history.redo(&mut self.pixels)
+	}
+
+	/// Undoes or redoes until `position` steps are applied.
+	pub fn go_to_history(&mut self, position: usize) {
+		if self.prepare_history() {
+			self.history.go_to(position, &mut self.pixels);
+		}
+	}
+
+	/// Puts back the selected Object. History can't be used while an Object is moving.
+	fn prepare_history(&mut self) -> bool {
+		match self.event {
+			Some(CanvasEvent::MovingObject(_) | CanvasEvent::ResizingObject(..) | CanvasEvent::MovingNode(..)) => false,
+
+			// Selected Objects might not exist afterwards.
+			Some(CanvasEvent::Selecting(_) | CanvasEvent::Selection(_)) => {
+				self.event = None;
+				true
+			}
+
+			Some(CanvasEvent::SelectedObject(id)) => {
+				self.pixels.insert_object_cells_by_id(id);
+				self.event = None;
+				true
+			}
+
+			_ => true
+		}
+	}
+
+	/// Replaces the current event. Returns false if an Object is being held.
+	pub fn set_event(&mut self, event: Option<CanvasEvent>) -> bool {
+		match self.event {
+			Some(CanvasEvent::MovingObject(_) | CanvasEvent::ResizingObject(..) | CanvasEvent::MovingNode(..)) => return false,
+
+			Some(CanvasEvent::SelectedObject(id)) => self.pixels.insert_object_cells_by_id(id),
+
+			_ => ()
+		}
+
+		self.event = event;
+
+		true
+	}
+
+	/// The Router keeps the color which is being painted with.
+	pub fn select_tool(&mut self, tool: CanvasTool) -> bool {
+		let event = match tool {
+			CanvasTool::Eraser | CanvasTool::NetSelect => CanvasEvent::Tooling(tool),
+
+			CanvasTool::Router => {
+				let color = match self.event {
+					Some(CanvasEvent::Painting(color) | CanvasEvent::Routing { color, .. }) => color,
+					_ => self.pixels.palette[0].0
+				};
+
+				CanvasEvent::Routing { color, from: None }
+			}
+		};
+
+		self.set_event(Some(event))
+	}
+
+	/// Paints with the palette color. Changes the color of the Router instead if it's in use.
+	pub fn select_palette(&mut self, index: usize) -> bool {
+		let Some(&(color, _)) = self.pixels.palette.get(index) else { return false };
+
+		let event = match self.event {
+			Some(CanvasEvent::Routing { .. }) => CanvasEvent::Routing { color, from: None },
+			_ => CanvasEvent::Painting(color)
+		};
+
+		self.set_event(Some(event))
+	}
+
+	/// Changes the color of every wire of the net and records it.
+	pub fn recolor_net(&mut self, net: &[CellPos], index: usize) {
+		let mut changes = Vec::new();
+
+		for &cell in net {
+			set_wire_cell(&mut self.pixels, &mut changes, cell, Some(index));
+		}
+
+		self.history.push_step(changes);
+	}
+
+	pub fn delete_net(&mut self, net: &[CellPos]) {
+		let mut changes = Vec::new();
+
+		for &cell in net {
+			set_wire_cell(&mut self.pixels, &mut changes, cell, None);
+		}
+
+		self.history.push_step(changes);
+	}
+
+	/// Routes a wire between the two Node cells and records it. Returns why it failed.
+	fn route_wire(&mut self, color: PixelColor, from: CellPos, to: CellPos) -> std::result::Result<(), String> {
+		let Some(palette_index) = self.pixels.palette.iter().position(|p| p.0 == color) else {
+			return Err(String::from("The color isn't in the palette."));
+		};
+
+		let (Some(output), Some(input)) = (route::find_node(&self.pixels, from), route::find_node(&self.pixels, to)) else {
+			return Err(String::from("Click on a Node."));
+		};
+
+		let path = route::route_between(&self.pixels, output, input, palette_index)?;
+
+		let mut changes = Vec::new();
+
+		for cell in path {
+			set_wire_cell(&mut self.pixels, &mut changes, cell, Some(palette_index));
+		}
+
+		self.history.push_step(changes);
+
+		Ok(())
+	}
+
+	/// Runs an edit of a single cell and records what happened to its wire.
+	fn edit_wire<F: FnOnce(&mut PixelBackground)>(&mut self, cell: CellPos, edit: F) {
+		let wire_index = |pixels: &PixelBackground| pixels.cells.get(&cell).and_then(|p| p.type_of.get_wire_palette_index());
+
+		let before = wire_index(&self.pixels);
+		edit(&mut self.pixels);
+		let after = wire_index(&self.pixels);
+
+		if before != after {
+			self.history.record(Change::Wire { cell, before, after });
+		}
+	}
+
+	/// Places the moving Object and records it along with the wires it replaced.
+	fn place_object(&mut self, object_id: ObjectId) {
+		let object = self.pixels.get_object_by_id(object_id).unwrap();
+
+		let mut changes: Vec<Change> = object.pixel_map()
+			.pixel_positions(object.get_cell_pos())
+			.into_iter()
+			.filter_map(|cell| {
+				self.pixels.cells.get(&cell)
+					.and_then(|p| p.type_of.get_wire_palette_index())
+					.map(|index| Change::Wire { cell, before: Some(index), after: None })
+			})
+			.collect();
+
+		match self.moving_from.take() {
+			Some(from) if from != object.get_cell_pos() => changes.push(Change::MoveObject {
+				id: object_id,
+				type_of: object.get_object_state().type_of,
+				from,
+				to: object.get_cell_pos()
+			}),
+
+			Some(_) => (),
+
+			None => changes.push(Change::AddObject(object_to_json(object)))
+		}
+
+		self.pixels.insert_object_cells_by_id(object_id);
+		self.history.push_step(changes);
+	}
+
+	/// Edits the selected or moving Object and records it. Nothing happens if it would overlap another Object.
+	fn edit_object<F: FnOnce(&mut ObjectState)>(&mut self, object_id: ObjectId, edit: F) {
+		if let Some(before) = self.try_edit_object(object_id, edit) {
+			self.record_object_edit(object_id, before);
+		}
+	}
+
+	/// Edits the Object if it still fits afterwards. Returns the Object from before the edit.
+	fn try_edit_object<F: FnOnce(&mut ObjectState)>(&mut self, object_id: ObjectId, edit: F) -> Option<ObjectNormalJson> {
+		let object = self.pixels.get_object_by_id_mut(object_id)?;
+
+		let before = object_to_json(&**object);
+
+		edit(object.get_object_state_mut());
+
+		self.pixels.reindex_object(object_id);
+
+		if !self.pixels.is_valid_object_pos(object_id) {
+			if let Some(object) = self.pixels.get_object_by_id_mut(object_id) {
+				*object = create_object_from_normal_json(object_id, before);
+			}
+
+			self.pixels.reindex_object(object_id);
+
+			return None;
+		}
+
+		Some(before)
+	}
+
+	fn record_object_edit(&mut self, object_id: ObjectId, before: ObjectNormalJson) {
+		let Some(object) = self.pixels.get_object_by_id(object_id) else { return };
+
+		let after = object_to_json(object);
+		let cells = object.pixel_map().pixel_positions(object.get_cell_pos());
+
+		if let Some(CanvasEvent::MovingObject(_)) = self.event {
+			// New Objects are recorded once they're placed.
+			if self.moving_from.is_some() {
+				self.history.push_step(vec![Change::EditObject { before, after }]);
+			}
+		} else {
+			// Wires under it would be replaced once it's placed back.
+			let mut changes = Vec::new();
+
+			for cell in cells {
+				set_wire_cell(&mut self.pixels, &mut changes, cell, None);
+			}
+
+			changes.push(Change::EditObject { before, after });
+
+			self.history.push_step(changes);
 		}
 	}
+
+	/// Changes a setting of the Object from the properties panel and records it.
+	pub fn set_object_property(&mut self, object_id: ObjectId, key: &str, value: PropertyValue) {
+		let Some(object) = self.pixels.get_object_by_id_mut(object_id) else { return };
+
+		let before = object_to_json(&**object);
+
+		if object.set_property(key, value) {
+			self.pixels.reindex_object(object_id);
+			self.record_object_edit(object_id, before);
+		}
+	}
+
+	/// Saves the selection into the clipboard.
+	fn copy_selection(&self, selection: &Selection) -> Result<()> {
+		let json = serde_json::to_string(&selection.to_json(&self.pixels))?;
+
+		if let Some(storage) = window().local_storage()? {
+			storage.set_item(CLIPBOARD_KEY, &json)?;
+		}
+
+		Ok(())
+	}
+
+	/// Pastes the clipboard with its top left at `cell`. The pasted Objects and Wires become the selection.
+	fn paste(&mut self, cell: CellPos) -> Result<()> {
+		let json = match window().local_storage()?.map(|s| s.get_item(CLIPBOARD_KEY)).transpose()?.flatten() {
+			Some(v) => serde_json::from_str(&v)?,
+			None => return Ok(())
+		};
+
+		if !can_insert_json(&self.pixels, &json, cell) {
+			statics::create_notification("Paste", NotificationType::Info("Not enough room to paste here.".into()), 1000 * 3)?;
+			return Ok(());
+		}
+
+		let (objects, wires) = insert_state_json_at(self, json, cell)?;
+
+		let selection = Selection::from_parts(&self.pixels, objects, wires);
+
+		if !selection.is_empty() {
+			self.event = Some(CanvasEvent::Selection(selection));
+		}
+
+		Ok(())
+	}
+
+	/// Deletes the moving Object. Only recorded if it was on the Canvas before.
+	fn remove_object(&mut self, object_id: ObjectId) {
+		if let Some(from) = self.moving_from.take() {
+			let mut json = object_to_json(self.pixels.get_object_by_id(object_id).unwrap());
+			json.pos = from;
+
+			self.history.push_step(vec![Change::DeleteObject(json)]);
+		}
+
+		self.pixels.delete_object(object_id);
+	}
 }
 
 impl EditorState for CanvasState {
 	fn init(&mut self, editor: &mut InnerEditor) -> Result<()> {
 		if let Some(ui) = editor.main_ui.as_ref() {
 			ui.write()?.sidebar.init_item_containers()?;
+			ui.write()?.sidebar.update_palette()?;
 			ui.write()?.sidebar.render_all();
 		}
 
@@ -82,35 +475,46 @@ impl EditorState for CanvasState {
 
 		let cursor = editor.view_opts.cursor();
 
+		// Background and cells
+		editor.layer.draw(&self.pixels, &editor.view_opts, ctx)?;
+
 		ctx.translate(
 			-editor.view_opts.get_canvas_drag_x(),
 			-editor.view_opts.get_canvas_drag_y()
 		)?;
 
-		let cells_in_view = editor.view_opts.cells_in_view();
+		// Object labels
+		self.pixels.render(editor)?;
 
-		{ // Background
-			ctx.set_fill_style(&JsValue::from_str("#2C2C2C"));
+		if is_editor_running() {
+			self.signals.render(&self.pixels, &editor.view_opts, ctx)?;
+		}
 
+		{ // Highlighted Cells
 			let pixel_size = editor.view_opts.pixel_size as f64;
 
-			for pos_x in 0..cells_in_view.2 {
-				for pos_y in 0..cells_in_view.3 {
-					let is_odd = (cells_in_view.0 + cells_in_view.1 + pos_x + pos_y + 1) % 2 == 1;
+			ctx.set_fill_style(&JsValue::from_str("rgba(234, 181, 45, 0.45)"));
 
-					if is_odd {
-						ctx.fill_rect(
-							(cells_in_view.0 as f64 + pos_x as f64) * pixel_size,
-							(cells_in_view.1 as f64 + pos_y as f64) * pixel_size,
-							pixel_size,
-							pixel_size
-						);
-					}
+			if self.critical_path_revision == self.history.revision() {
+				for &(x, y) in &self.critical_path {
+					ctx.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
 				}
 			}
-		}
 
-		self.pixels.render(editor)?;
+			ctx.set_fill_style(&JsValue::from_str("rgba(233, 46, 58, 0.45)"));
+
+			for &(x, y) in &self.highlighted {
+				ctx.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+			}
+
+			if let Some(cell) = self.highlighted_net {
+				ctx.set_fill_style(&JsValue::from_str("rgba(240, 240, 240, 0.45)"));
+
+				for (x, y) in net::flood_net(&self.pixels, cell) {
+					ctx.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+				}
+			}
+		}
 
 		if let Some(event) = self.event.as_ref() {
 			match event {
@@ -119,11 +523,49 @@ impl EditorState for CanvasState {
 
 					let display = PixelDisplay(0);
 
-					let (cell_x, cell_y) = cursor.cell_usize_checked();
+					let (cell_x, cell_y) = cursor.cell();
 					display.render(cell_x, cell_y, pixel_size, *p, ctx);
 				}
 
+				CanvasEvent::Net(net) => {
+					let pixel_size = editor.view_opts.pixel_size as f64;
+
+					ctx.set_fill_style(&JsValue::from_str("rgba(63, 143, 209, 0.45)"));
+
+					for &(x, y) in net {
+						ctx.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+					}
+				}
+
+				&CanvasEvent::Drawing(color, start) => {
+					let pixel_size = editor.view_opts.pixel_size as f64;
+
+					ctx.set_global_alpha(0.6);
+					ctx.set_fill_style(&JsValue::from_str(&color.get_string_color()));
+
+					// Cells which `set_wire` would skip aren't shown.
+					for (x, y) in self.wire_mode.cells(start, cursor.cell()) {
+						if self.pixels.cells.get(&(x, y)).is_none_or(|p| p.type_of.is_wire()) {
+							ctx.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+						}
+					}
+
+					ctx.set_global_alpha(1.0);
+				}
+
+				&CanvasEvent::Routing { color, from } => {
+					let pixel_size = editor.view_opts.pixel_size as f64;
+
+					ctx.set_fill_style(&JsValue::from_str(&color.get_string_color()));
+
+					for (x, y) in from.into_iter().chain(std::iter::once(cursor.cell())) {
+						ctx.fill_rect(x as f64 * pixel_size + 3.0, y as f64 * pixel_size + 3.0, pixel_size - 6.0, pixel_size - 6.0);
+					}
+				}
+
 				CanvasEvent::Tooling(tool) => match tool {
+					CanvasTool::Router | CanvasTool::NetSelect => (),
+
 					CanvasTool::Eraser => {
 						let pixel_size = editor.view_opts.pixel_size as f64;
 
@@ -139,11 +581,22 @@ impl EditorState for CanvasState {
 				}
 
 				// Selected Object
-				CanvasEvent::SelectedObject(id) => { // TODO: Show deleted Nodes.
+				CanvasEvent::SelectedObject(id) |
+				CanvasEvent::ResizingObject(id, _) |
+				CanvasEvent::MovingNode(id, ..) => { // TODO: Show deleted Nodes.
 					if let Some(obj) = self.pixels.get_object_by_id(*id) {
 						let cell_pos = obj.get_cell_pos();
 
 						obj.pixel_map().render(cell_pos, true, &editor.view_opts, &self.pixels.palette, ctx)?;
+
+						// Resize Handle
+						if obj.as_editable_ref().is_some() {
+							let pixel_size = editor.view_opts.pixel_size as f64;
+							let (x, y) = resize_handle(obj);
+
+							ctx.set_fill_style(&JsValue::from_str("#3f8fd1"));
+							ctx.fill_rect(x as f64 * pixel_size + 3.0, y as f64 * pixel_size + 3.0, pixel_size - 6.0, pixel_size - 6.0);
+						}
 					}
 				}
 
@@ -154,6 +607,24 @@ impl EditorState for CanvasState {
 						obj.pixel_map().render(cell_pos, true, &editor.view_opts, &self.pixels.palette, ctx)?;
 					}
 				}
+
+				&CanvasEvent::Selecting(start) => {
+					render_selection_rect(start, cursor.cell(), editor, ctx);
+				}
+
+				CanvasEvent::Selection(selection) => {
+					let pixel_size = editor.view_opts.pixel_size as f64;
+
+					ctx.set_fill_style(&JsValue::from_str("rgba(63, 143, 209, 0.45)"));
+
+					for (x, y) in selection.cells(&self.pixels).into_iter().filter_map(|c| offset_cell_pos(c, selection.offset)) {
+						ctx.fill_rect(x as f64 * pixel_size, y as f64 * pixel_size, pixel_size, pixel_size);
+					}
+
+					if let (Some(min), Some(max)) = (offset_cell_pos(selection.min, selection.offset), offset_cell_pos(selection.max, selection.offset)) {
+						render_selection_rect(min, max, editor, ctx);
+					}
+				}
 			}
 		}
 
@@ -180,7 +651,7 @@ impl EditorState for CanvasState {
 			// Cells in view
 			ctx.fill_text(&format!(
 				"In View: {:?}",
-				cells_in_view
+				editor.view_opts.cells_in_view()
 			), 1.0, 16.0)?;
 
 			// Cells in view
@@ -224,7 +695,7 @@ impl EditorState for CanvasState {
 fn update_running(this: &mut CanvasState, editor: &mut InnerEditor, editor_event: &EditorEvent) -> Result<()> {
 	let cursor = editor.view_opts.cursor();
 
-	let cell = cursor.cell_usize_checked();
+	let cell = cursor.cell();
 
 	let mut update_obj_pixels = None;
 
@@ -271,11 +742,27 @@ fn update_running(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event: &EditorEvent) -> Result<()> {
 	let cursor = editor.view_opts.cursor();
 
+	match editor_event {
+		// New press starts a new history step.
+		EditorEvent::MouseDown(_) => this.history.close(),
+
+		&EditorEvent::Command(command) => return run_command(this, command, cursor.cell()),
+
+		_ => ()
+	}
+
+	if matches!(this.event, Some(CanvasEvent::Selecting(_) | CanvasEvent::Selection(_))) {
+		update_selection(this, editor, editor_event)?;
+	}
+
 	if let Some(event) = this.event.as_ref() {
 		match event {
+			// Handled above
+			CanvasEvent::Selecting(_) | CanvasEvent::Selection(_) => (),
+
 			// Placing Wires
 			CanvasEvent::Painting(p) => {
-				let cell = cursor.cell_usize_checked();
+				let cell = cursor.cell();
 
 				match editor_event {
 					// Unset
@@ -286,12 +773,48 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 
 					// Place Wiring
 					EditorEvent::MouseClick(e) if e.button.left() => {
-						this.pixels.set_wire(cell, *p);
+						let p = *p;
+						this.edit_wire(cell, |pixels| pixels.set_wire(cell, p));
+						return Ok(());
+					}
+
+					// Start dragging out a shape.
+					EditorEvent::MouseMove(e) if e.button.left() && editor.view_opts.is_mouse_dragging() && this.wire_mode != WireMode::Freehand => {
+						if let Some(start) = editor.view_opts.cursor_start_drag_cell {
+							this.event = Some(CanvasEvent::Drawing(*p, start));
+						}
+
 						return Ok(());
 					}
 
 					EditorEvent::MouseMove(e) if e.button.left() && editor.view_opts.is_mouse_dragging() => {
-						this.pixels.set_wire(cell, *p);
+						let p = *p;
+						this.edit_wire(cell, |pixels| pixels.set_wire(cell, p));
+						return Ok(());
+					}
+
+					_ => ()
+				}
+			}
+
+			// Drawing a shape of Wires
+			&CanvasEvent::Drawing(color, start) => {
+				match editor_event {
+					// Cancel
+					EditorEvent::MouseClick(e) if e.button.right() => {
+						this.event = Some(CanvasEvent::Painting(color));
+						return Ok(());
+					}
+
+					// Released
+					EditorEvent::MouseClick(e) if e.button.left() => {
+						for cell in this.wire_mode.cells(start, cursor.cell()) {
+							this.edit_wire(cell, |pixels| pixels.set_wire(cell, color));
+						}
+
+						this.history.close();
+						this.event = Some(CanvasEvent::Painting(color));
+
 						return Ok(());
 					}
 
@@ -303,7 +826,7 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 			CanvasEvent::Tooling(tool) => match tool {
 				// Eraser
 				CanvasTool::Eraser => {
-					let cell_pos = cursor.cell_usize_checked();
+					let cell_pos = cursor.cell();
 
 					match editor_event {
 						// Unset
@@ -314,14 +837,14 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 
 						EditorEvent::MouseClick(e) if e.button.left() => {
 							if this.pixels.is_cell_wire(cell_pos) {
-								this.pixels.delete_cell(cell_pos);
+								this.edit_wire(cell_pos, |pixels| pixels.delete_cell(cell_pos));
 							}
 							return Ok(());
 						}
 
 						EditorEvent::MouseMove(e) if e.button.left() && editor.view_opts.is_mouse_dragging() => {
 							if this.pixels.is_cell_wire(cell_pos) {
-								this.pixels.delete_cell(cell_pos);
+								this.edit_wire(cell_pos, |pixels| pixels.delete_cell(cell_pos));
 							}
 							return Ok(());
 						}
@@ -329,16 +852,135 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 						_ => ()
 					}
 				}
+
+				// Becomes `CanvasEvent::Routing` once it's selected.
+				CanvasTool::Router => (),
+
+				CanvasTool::NetSelect => match editor_event {
+					// Unset
+					EditorEvent::MouseClick(e) if e.button.right() => {
+						this.event.take();
+						return Ok(());
+					}
+
+					EditorEvent::MouseClick(e) if e.button.left() => return select_net(this, cursor.cell()),
+
+					_ => ()
+				}
+			}
+
+			// Selected Net
+			CanvasEvent::Net(_) => match editor_event {
+				EditorEvent::MouseClick(e) if e.button.right() => {
+					this.event = Some(CanvasEvent::Tooling(CanvasTool::NetSelect));
+					return Ok(());
+				}
+
+				EditorEvent::MouseClick(e) if e.button.left() => return select_net(this, cursor.cell()),
+
+				_ => ()
+			}
+
+			// Routing Wires
+			&CanvasEvent::Routing { color, from } => {
+				let cell = cursor.cell();
+
+				match editor_event {
+					// Unset
+					EditorEvent::MouseClick(e) if e.button.right() => {
+						this.event = from.map(|_| CanvasEvent::Routing { color, from: None });
+						return Ok(());
+					}
+
+					EditorEvent::MouseClick(e) if e.button.left() => {
+						let Some(from) = from else {
+							match route::find_node(&this.pixels, cell) {
+								Some(node) if node.direction == NodeDirection::Output => {
+									this.event = Some(CanvasEvent::Routing { color, from: Some(cell) });
+								}
+
+								_ => statics::create_notification("Router", NotificationType::Info("Click on an output Node to start routing.".into()), 1000 * 3)?
+							}
+
+							return Ok(());
+						};
+
+						this.history.close();
+
+						match this.route_wire(color, from, cell) {
+							Ok(()) => this.event = Some(CanvasEvent::Routing { color, from: None }),
+							Err(e) => statics::create_notification("Router", NotificationType::ErrorStr(e), 1000 * 5)?
+						}
+
+						return Ok(());
+					}
+
+					_ => ()
+				}
 			}
 
 			// Selected Object
 			&CanvasEvent::SelectedObject(object_id) => {
+				let cell = cursor.cell();
+
+				let obj = this.pixels.get_object_by_id(object_id).unwrap();
+
+				let are_nodes_editable = obj.as_editable_ref().is_some_and(Editable::are_nodes_editable);
+				let slot = obj.get_object_state().side_at_cell(cell).filter(|_| are_nodes_editable);
+				let node = slot.and_then(|side| obj.get_object_state().node_index_at_side(side));
+
+				match editor_event {
+					// Add a Node or flip its direction.
+					EditorEvent::MouseClick(e) if e.button.left() && slot.is_some() => {
+						let accepts = obj.as_editable_ref().and_then(|e| e.editable_values().first().copied()).unwrap_or(NodeValueTypes::Gpio);
+
+						match (node, slot) {
+							(Some(index), _) => this.edit_object(object_id, |s| s.flip_node_direction(index)),
+							(None, Some(side)) => this.edit_object(object_id, |s| s.insert_node(Node::new_input(side, accepts))),
+							_ => ()
+						}
+
+						return Ok(());
+					}
+
+					// Remove Node
+					EditorEvent::MouseClick(e) if e.button.right() && node.is_some() => {
+						if let Some(index) = node {
+							this.edit_object(object_id, |s| s.remove_node(index));
+						}
+
+						return Ok(());
+					}
+
+					// Start Resizing or Moving a Node
+					EditorEvent::MouseMove(_) if editor.view_opts.is_last_button_press_left() && editor.view_opts.is_mouse_dragging() => {
+						let start = editor.view_opts.cursor_start_drag_cell;
+
+						if obj.as_editable_ref().is_some() && start == Some(resize_handle(obj)) {
+							this.event = Some(CanvasEvent::ResizingObject(object_id, object_to_json(obj)));
+							return Ok(());
+						}
+
+						let start_node = start
+							.filter(|_| are_nodes_editable)
+							.and_then(|c| obj.get_object_state().side_at_cell(c))
+							.and_then(|side| obj.get_object_state().node_index_at_side(side));
+
+						if let Some(index) = start_node {
+							this.event = Some(CanvasEvent::MovingNode(object_id, index, object_to_json(obj)));
+							return Ok(());
+						}
+					}
+
+					_ => ()
+				}
+
 				match editor_event {
 					// Unset Object
 					EditorEvent::MouseClick(e) if e.button.left() => {
 						let obj = this.pixels.get_object_by_id(object_id).unwrap();
 
-						if obj.get_cell_pos() != cursor.cell_usize_checked() {
+						if obj.get_cell_pos() != cursor.cell() {
 							this.pixels.insert_object_cells_by_id(object_id);
 							this.event.take();
 							return Ok(());
@@ -352,7 +994,8 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 						if let Some(cell) = editor.view_opts.cursor_start_drag_cell {
 							let obj = this.pixels.get_object_by_id(object_id).unwrap();
 
-							if obj.get_cell_pos() == cell && cursor.cell_usize_checked() != cell {
+							if obj.get_cell_pos() == cell && cursor.cell() != cell {
+								this.moving_from = Some(cell);
 								this.event = Some(CanvasEvent::MovingObject(object_id));
 							}
 						}
@@ -364,9 +1007,89 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 				}
 			}
 
+			// Resizing Object
+			CanvasEvent::ResizingObject(object_id, before) => {
+				let object_id = *object_id;
+
+				match editor_event {
+					EditorEvent::MouseMove(_) => {
+						let obj = this.pixels.get_object_by_id(object_id).unwrap();
+
+						let (pos_x, pos_y) = obj.get_cell_pos();
+						let (cell_x, cell_y) = cursor.cell();
+
+						let dimensions = Dimensions::checked(
+							(cell_x - pos_x).max(1) as usize,
+							(cell_y - pos_y).max(1) as usize
+						);
+
+						let dimensions = obj.as_editable_ref().map_or(dimensions, |e| e.clamp_dimensions(dimensions));
+
+						if dimensions != obj.get_dimensions() && obj.get_object_state().can_fit_nodes(dimensions) {
+							this.try_edit_object(object_id, |s| s.set_dimensions(dimensions));
+						}
+
+						return Ok(());
+					}
+
+					// Released
+					EditorEvent::MouseClick(e) if e.button.left() => {
+						let before = before.clone();
+						let has_changed = this.pixels.get_object_by_id(object_id).is_some_and(|o| o.get_dimensions() != before.dim);
+
+						this.event = Some(CanvasEvent::SelectedObject(object_id));
+
+						if has_changed {
+							this.record_object_edit(object_id, before);
+						}
+
+						return Ok(());
+					}
+
+					_ => ()
+				}
+			}
+
+			// Moving Node
+			CanvasEvent::MovingNode(object_id, index, before) => {
+				let (object_id, index) = (*object_id, *index);
+
+				match editor_event {
+					EditorEvent::MouseMove(_) => {
+						let state = this.pixels.get_object_by_id(object_id).unwrap().get_object_state();
+
+						let side = state.side_at_cell(cursor.cell())
+							.filter(|&side| state.node_index_at_side(side).is_none());
+
+						if let Some(side) = side {
+							this.try_edit_object(object_id, |s| s.move_node(index, side));
+						}
+
+						return Ok(());
+					}
+
+					// Released
+					EditorEvent::MouseClick(e) if e.button.left() => {
+						let before = before.clone();
+						let has_changed = this.pixels.get_object_by_id(object_id)
+							.is_some_and(|o| Some(o.get_object_state().nodes[index].side) != before.nodes.get(index).map(|n| n.side));
+
+						this.event = Some(CanvasEvent::SelectedObject(object_id));
+
+						if has_changed {
+							this.record_object_edit(object_id, before);
+						}
+
+						return Ok(());
+					}
+
+					_ => ()
+				}
+			}
+
 			// Moving Object
 			&CanvasEvent::MovingObject(object_id) => {
-				let cell = cursor.cell_usize_checked();
+				let cell = cursor.cell();
 
 				match editor_event {
 					// Move object and check if can be moved there.
@@ -376,18 +1099,20 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 						let size = obj.get_dimensions();
 
 						let new_cell_pos = (
-							cell.0.saturating_sub(size.width() / 2),
-							cell.1.saturating_sub(size.height() / 2)
+							cell.0 - size.width() as i32 / 2,
+							cell.1 - size.height() as i32 / 2
 						);
 
 						let last_cell_pos = obj.get_cell_pos();
 
 						if new_cell_pos != last_cell_pos {
 							obj.set_cell_pos(new_cell_pos);
+							this.pixels.reindex_object(object_id);
 						}
 
 						if new_cell_pos != last_cell_pos && !this.pixels.is_valid_object_pos(object_id) {
 							this.pixels.get_object_by_id_mut(object_id).unwrap().set_cell_pos(last_cell_pos);
+							this.pixels.reindex_object(object_id);
 						}
 
 						return Ok(());
@@ -395,14 +1120,14 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 
 					// Remove
 					EditorEvent::MouseClick(e) if e.button.right() => {
-						this.pixels.delete_object(object_id);
+						this.remove_object(object_id);
 						this.event.take();
 						return Ok(());
 					}
 
 					// Placed Object. Remove Wires in Object Cells. Unset Event
 					EditorEvent::MouseClick(e) if e.button.left() => {
-						this.pixels.insert_object_cells_by_id(object_id);
+						this.place_object(object_id);
 						this.event.take();
 						return Ok(());
 					}
@@ -414,7 +1139,7 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 	} else {
 		match editor_event {
 			EditorEvent::MouseClick(e) if e.button.left() => {
-				let cell = cursor.cell_usize_checked();
+				let cell = cursor.cell();
 
 				if let Some(id) = this.pixels.get_object_in_cell(cell).map(|o| o.get_id()) {
 					this.pixels.delete_object_cells(id);
@@ -423,6 +1148,16 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 				}
 			}
 
+			// Start Selecting
+			EditorEvent::MouseMove(_) if editor.view_opts.is_last_button_press_left() && editor.view_opts.is_mouse_dragging() => {
+				if let Some(start) = editor.view_opts.cursor_start_drag_cell {
+					if this.pixels.get_object_in_cell(start).is_none() {
+						this.event = Some(CanvasEvent::Selecting(start));
+						return Ok(());
+					}
+				}
+			}
+
 			_ => ()
 		}
 	}
@@ -434,4 +1169,205 @@ fn update_editing(this: &mut CanvasState, editor: &mut InnerEditor, editor_event
 	}
 
 	Ok(())
-}
\ No newline at end of file
+}
+
+/// Selects the net of the wire in the cell and shows what can be done with it. Goes back to the tool otherwise.
+fn select_net(this: &mut CanvasState, cell: CellPos) -> Result<()> {
+	let net = net::flood_net(&this.pixels, cell);
+
+	if net.is_empty() {
+		this.event = Some(CanvasEvent::Tooling(CanvasTool::NetSelect));
+		return Ok(());
+	}
+
+	this.event = Some(CanvasEvent::Net(net));
+
+	net_popup()
+}
+
+
+fn run_command(this: &mut CanvasState, command: Command, cell: CellPos) -> Result<()> {
+	match (command, this.event.take()) {
+		(Command::Undo, event) => {
+			this.event = event;
+			this.undo();
+		}
+
+		(Command::Redo, event) => {
+			this.event = event;
+			this.redo();
+		}
+
+		(Command::Eraser | Command::Router | Command::NetSelect | Command::Palette(_) | Command::Cancel, event) => {
+			this.event = event;
+
+			match command {
+				Command::Eraser => this.select_tool(CanvasTool::Eraser),
+				Command::Router => this.select_tool(CanvasTool::Router),
+				Command::NetSelect => this.select_tool(CanvasTool::NetSelect),
+				Command::Palette(index) => this.select_palette(index as usize),
+				_ => this.set_event(None)
+			};
+		}
+
+		(Command::NextWireMode | Command::FlipBend, event) => {
+			this.event = event;
+
+			this.wire_mode = match command {
+				Command::NextWireMode => this.wire_mode.next(),
+				_ => this.wire_mode.flipped_bend()
+			};
+		}
+
+		(Command::RotateClockwise | Command::RotateCounterClockwise | Command::Mirror, event) => {
+			this.event = event;
+
+			if let Some(CanvasEvent::SelectedObject(object_id) | CanvasEvent::MovingObject(object_id)) = this.event {
+				let transform: fn(&mut ObjectState) = match command {
+					Command::RotateClockwise => ObjectState::rotate_clockwise,
+					Command::RotateCounterClockwise => ObjectState::rotate_counter_clockwise,
+					_ => ObjectState::mirror
+				};
+
+				this.edit_object(object_id, transform);
+			}
+		}
+
+		(Command::Paste, None | Some(CanvasEvent::Selection(_))) => {
+			this.history.close();
+
+			this.paste(cell)?;
+		}
+
+		(Command::Copy | Command::Cut, Some(CanvasEvent::Selection(selection))) => {
+			this.copy_selection(&selection)?;
+
+			if command == Command::Cut {
+				let changes = selection.delete(&mut this.pixels);
+				this.history.push_step(changes);
+			} else {
+				this.event = Some(CanvasEvent::Selection(selection));
+			}
+		}
+
+		(Command::Delete, Some(CanvasEvent::Net(net))) => {
+			this.delete_net(&net);
+			this.event = Some(CanvasEvent::Tooling(CanvasTool::NetSelect));
+		}
+
+		(Command::Delete, Some(CanvasEvent::Selection(selection))) => {
+			let changes = selection.delete(&mut this.pixels);
+			this.history.push_step(changes);
+		}
+
+		(Command::Delete, Some(CanvasEvent::SelectedObject(object_id))) => {
+			// Its cells were removed once it was selected.
+			this.pixels.insert_object_cells_by_id(object_id);
+
+			if let Some(object) = this.pixels.get_object_by_id(object_id) {
+				let change = Change::DeleteObject(object_to_json(object));
+
+				let mut changes = Vec::new();
+				apply_change(&mut this.pixels, &mut changes, change);
+
+				this.history.push_step(changes);
+			}
+		}
+
+		(_, event) => this.event = event
+	}
+
+	Ok(())
+}
+
+
+fn update_selection(this: &mut CanvasState, editor: &mut InnerEditor, editor_event: &EditorEvent) -> Result<()> {
+	let cursor = editor.view_opts.cursor();
+	let cell = cursor.cell();
+
+	match this.event.take() {
+		Some(CanvasEvent::Selecting(start)) => match editor_event {
+			// Finished the rectangle.
+			EditorEvent::MouseClick(e) if e.button.left() => {
+				let selection = Selection::from_corners(&this.pixels, start, cell);
+
+				if !selection.is_empty() {
+					this.event = Some(CanvasEvent::Selection(selection));
+				}
+			}
+
+			EditorEvent::MouseClick(e) if e.button.right() => (),
+
+			_ => this.event = Some(CanvasEvent::Selecting(start))
+		}
+
+		Some(CanvasEvent::Selection(mut selection)) => {
+			match editor_event {
+				// Drag the selection. Stays at the last position where it fit.
+				EditorEvent::MouseMove(_) if editor.view_opts.is_last_button_press_left() && editor.view_opts.is_mouse_dragging() => {
+					if let Some(start) = editor.view_opts.cursor_start_drag_cell.filter(|&c| selection.contains(c)) {
+						let (cell_x, cell_y) = cursor.cell();
+
+						let offset = (cell_x - start.0, cell_y - start.1);
+
+						if selection.can_move_by(&this.pixels, offset) {
+							selection.offset = offset;
+						}
+					}
+				}
+
+				// Dropped
+				EditorEvent::MouseClick(e) if e.button.left() && selection.offset != (0, 0) => {
+					let offset = selection.offset;
+					let changes = selection.move_by(&mut this.pixels, offset);
+
+					this.history.push_step(changes);
+				}
+
+				// Clicked outside. Unselect.
+				EditorEvent::MouseClick(e) if e.button.left() && !selection.contains(cell) => return Ok(()),
+				EditorEvent::MouseClick(e) if e.button.right() => return Ok(()),
+
+				_ => ()
+			}
+
+			this.event = Some(CanvasEvent::Selection(selection));
+		}
+
+		event => this.event = event
+	}
+
+	Ok(())
+}
+
+
+fn render_selection_rect(a: CellPos, b: CellPos, editor: &InnerEditor, ctx: &CanvasRenderingContext2d) {
+	let pixel_size = editor.view_opts.pixel_size as f64;
+
+	let (min_x, min_y) = (a.0.min(b.0) as f64, a.1.min(b.1) as f64);
+	let (max_x, max_y) = (a.0.max(b.0) as f64 + 1.0, a.1.max(b.1) as f64 + 1.0);
+
+	ctx.set_stroke_style(&JsValue::from_str("#3f8fd1"));
+	ctx.set_line_width(2.0);
+
+	ctx.stroke_rect(min_x * pixel_size, min_y * pixel_size, (max_x - min_x) * pixel_size, (max_y - min_y) * pixel_size);
+}
+
+
+/// Top left and bottom right of the cells.
+fn cell_bounds<I: IntoIterator<Item = CellPos>>(cells: I) -> Option<(CellPos, CellPos)> {
+	cells.into_iter().fold(None, |bounds, (x, y)| {
+		Some(match bounds {
+			Some(((min_x, min_y), (max_x, max_y))) => ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))),
+			None => ((x, y), (x, y))
+		})
+	})
+}
+
+/// Cell past the bottom right corner of the Object. Never a Node.
+fn resize_handle(object: &dyn Renderable) -> CellPos {
+	let (x, y) = object.get_cell_pos();
+	let dimensions = object.get_dimensions();
+
+	(x + dimensions.width() as i32, y + dimensions.height() as i32)
+}
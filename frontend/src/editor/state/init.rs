@@ -5,7 +5,7 @@ use web_sys::{ProgressEvent, UrlSearchParams, XmlHttpRequest};
 
 use crate::{Error, InnerEditor, NotificationType, Result, config, error::DisplayError, statics::{get_editor_state, get_editor_state_mut, set_editor_state}, window};
 
-use super::{CanvasState, EditorState};
+use super::{CanvasState, EditorState, ViewerState};
 
 
 
@@ -150,6 +150,11 @@ impl EditorState for InitState {
 pub fn finish_loading() -> Result<()> {
 	let location = window().location();
 
+	// View an already-made canvas
+	if let Some(id) = location.pathname()?.strip_prefix("/view/").filter(|id| !id.is_empty()) {
+		return load_and_setup(id.to_string(), true);
+	}
+
 	let search = location.search()?;
 
 	if search.is_empty() || search.len() == 1 {
@@ -176,7 +181,7 @@ pub fn finish_loading() -> Result<()> {
 				))?;
 			} else {
 				// Try loading specified state
-				load_and_setup(id)?;
+				load_and_setup(id, false)?;
 			}
 		} else {
 			set_editor_state(Box::new(CanvasState::new()))?;
@@ -186,7 +191,8 @@ pub fn finish_loading() -> Result<()> {
 	Ok(())
 }
 
-fn load_and_setup(id: String) -> Result<()> {
+/// `is_viewing` opens it read-only.
+fn load_and_setup(id: String, is_viewing: bool) -> Result<()> {
 	log!("load_and_setup: {}", id);
 
 	config::load(id, Box::new(move |result: Option<CanvasJsonReqResp>| {
@@ -195,7 +201,7 @@ fn load_and_setup(id: String) -> Result<()> {
 
 			init.loading_canvas = Some(config);
 
-			create_state(init.loading_canvas.take().unwrap())?;
+			create_state(init.loading_canvas.take().unwrap(), is_viewing)?;
 		} else {
 			// TODO: Error screen?
 		}
@@ -204,12 +210,16 @@ fn load_and_setup(id: String) -> Result<()> {
 	}))
 }
 
-fn create_state(json: CanvasJsonReqResp) -> Result<()> {
+fn create_state(json: CanvasJsonReqResp, is_viewing: bool) -> Result<()> {
 	let config = config::create_config_from_json(json)?;
 
 	log!("create_state");
 
-	set_editor_state(Box::new(config))?;
+	if is_viewing {
+		set_editor_state(Box::new(ViewerState::new(config)))?;
+	} else {
+		set_editor_state(Box::new(config))?;
+	}
 
 	Ok(())
 }
\ No newline at end of file
@@ -5,8 +5,10 @@ use super::InnerEditor;
 
 pub mod canvas;
 pub mod init;
+pub mod viewer;
 
 pub use canvas::CanvasState;
+pub use viewer::ViewerState;
 use circuit_sim_common::config::StateInfo;
 use wasm_bindgen::JsValue;
 
@@ -33,6 +35,11 @@ pub trait EditorState {
 		"canvas"
 	}
 
+	/// Can't be edited or saved. Only ran.
+	fn is_read_only(&self) -> bool {
+		false
+	}
+
 	fn as_any_ref(&self) -> &dyn Any;
 	fn as_any_mut(&mut self) -> &mut dyn Any;
 
@@ -0,0 +1,135 @@
+use std::any::Any;
+
+use circuit_sim_common::config::StateInfo;
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+
+use crate::{
+	EditorEvent, InnerEditor, NotificationType, Result, body, window,
+	config::{create_state_from_json, try_from_editor_state},
+	editor::toggled_edit_mode,
+	statics::{self, get_editor_state, is_editor_running, set_editor_running}
+};
+
+use super::{CanvasState, EditorState};
+
+
+/// Body class while viewing. Hides the editing tools.
+const VIEWING_CLASS: &str = "editor-viewing";
+/// Body class while viewing as a logged in user. Shows the Fork button.
+const CAN_FORK_CLASS: &str = "editor-can-fork";
+
+
+/// Read-only Canvas opened from `/view/{id}`. It can be panned, zoomed and ran but not edited.
+pub struct ViewerState {
+	canvas: CanvasState
+}
+
+impl ViewerState {
+	pub fn new(canvas: CanvasState) -> Self {
+		Self { canvas }
+	}
+}
+
+impl EditorState for ViewerState {
+	fn init(&mut self, _: &mut InnerEditor) -> Result<()> {
+		body().class_list().add_1(VIEWING_CLASS)?;
+		body().class_list().toggle_with_force(CAN_FORK_CLASS, statics::has_user_info())?;
+
+		Ok(())
+	}
+
+	fn render(&self, editor: &InnerEditor) -> Result<()> {
+		self.canvas.render(editor)
+	}
+
+	fn update(&mut self, editor: &mut InnerEditor, editor_event: &EditorEvent) -> Result<()> {
+		// Only Objects can be clicked while running. Nothing can be edited.
+		if is_editor_running() {
+			self.canvas.update(editor, editor_event)?;
+		}
+
+		// Left dragging pans too while the Objects can't be clicked.
+		let is_panning = editor.view_opts.is_last_button_press_right() || !is_editor_running();
+
+		if is_panning && !editor.view_opts.is_dragging_canvas() && editor.view_opts.is_mouse_dragging() {
+			editor.view_opts.start_dragging_canvas();
+		}
+
+		Ok(())
+	}
+
+	fn is_read_only(&self) -> bool {
+		true
+	}
+
+
+	fn as_any_ref(&self) -> &dyn Any {
+		self
+	}
+
+	fn as_any_mut(&mut self) -> &mut dyn Any {
+		self
+	}
+
+
+	fn get_canvas_state(&self) -> Option<&CanvasState> {
+		Some(&self.canvas)
+	}
+
+	fn get_canvas_state_mut(&mut self) -> Option<&mut CanvasState> {
+		Some(&mut self.canvas)
+	}
+
+
+	fn set_state_info(&mut self, value: StateInfo) {
+		self.canvas.info = value;
+	}
+
+	fn get_state_info(&self) -> StateInfo {
+		self.canvas.info.clone()
+	}
+}
+
+
+/// Opens a copy of the viewed Canvas in the editor. It's owned by the logged in user once it's saved.
+pub fn fork_viewed_canvas() -> Result<()> {
+	if !statics::has_user_info() {
+		return statics::create_notification("Fork", NotificationType::Info(String::from("Log in to fork the Canvas.")), 1000 * 5);
+	}
+
+	let forked = {
+		let Some(viewer) = get_editor_state().as_any_ref().downcast_ref::<ViewerState>() else { return Ok(()) };
+
+		let mut info = StateInfo::from_user_id(statics::get_user_info().id, Date::now() as i64);
+		info.forked_id.clone_from(&viewer.canvas.info.canvas_id);
+		info.title.clone_from(&viewer.canvas.info.title);
+		info.description.clone_from(&viewer.canvas.info.description);
+
+		let mut forked = create_state_from_json(try_from_editor_state(&viewer.canvas)?, info)?;
+
+		// Only exists in this tab until it's saved.
+		forked.history.clear_saved();
+
+		forked
+	};
+
+	if is_editor_running() {
+		set_editor_running(false);
+		toggled_edit_mode()?;
+	}
+
+	remove_viewing_classes()?;
+
+	// Saving it puts its own Id into the location.
+	window().history()?.replace_state_with_url(&JsValue::NULL, "", Some("/edit"))?;
+
+	statics::set_editor_state(Box::new(forked))
+}
+
+/// Shows the editing tools again. `ViewerState::init` adds the classes back.
+pub fn remove_viewing_classes() -> Result<()> {
+	body().class_list().remove_2(VIEWING_CLASS, CAN_FORK_CLASS)?;
+
+	Ok(())
+}
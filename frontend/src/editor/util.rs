@@ -5,6 +5,14 @@ use web_sys::CanvasRenderingContext2d;
 use super::{MouseEvent, MouseButton};
 
 
+/// Smallest size a cell can be zoomed out to.
+pub const MIN_PIXEL_SIZE: usize = 10;
+/// Zooming to fit a few cells shouldn't blow them up to fill the screen.
+const MAX_FIT_PIXEL_SIZE: usize = 48;
+/// Empty cells kept around the content when zooming to fit.
+const FIT_PADDING: i32 = 2;
+
+
 // TODO: Make Static
 #[derive(Default, Clone)]
 pub struct ViewOptions {
@@ -58,7 +66,7 @@ impl ViewOptions {
 	pub fn on_mouse_down(&mut self, event: &MouseEvent) {
 		self.button_pressed = Some(event.button);
 		self.cursor_start_drag = Some((self.get_mouse_x(), self.get_mouse_y()));
-		self.cursor_start_drag_cell = Some(self.cursor().cell_usize_checked());
+		self.cursor_start_drag_cell = Some(self.cursor().cell());
 	}
 
 	pub fn update_zoom(&mut self, mut value: i32) {
@@ -70,11 +78,68 @@ impl ViewOptions {
 			if value > 0 {
 				self.pixel_size += value as usize;
 			} else {
-				self.pixel_size = self.pixel_size.saturating_sub(value.abs() as usize).max(10);
+				self.pixel_size = self.pixel_size.saturating_sub(value.unsigned_abs() as usize).max(MIN_PIXEL_SIZE);
 			}
 		}
 	}
 
+	/// Zooms while keeping whatever is under the fixed position in place.
+	pub fn zoom_at(&mut self, value: i32, fixed_x: f64, fixed_y: f64) {
+		let pixel_size = self.pixel_size as f64;
+
+		let cell_x = (fixed_x + self.canvas_drag_x) / pixel_size;
+		let cell_y = (fixed_y + self.canvas_drag_y) / pixel_size;
+
+		self.update_zoom(value);
+
+		let pixel_size = self.pixel_size as f64;
+
+		self.set_drag_pos(cell_x * pixel_size - fixed_x, cell_y * pixel_size - fixed_y);
+	}
+
+	/// Zooms around the middle of the view.
+	pub fn zoom_at_center(&mut self, value: i32) {
+		self.zoom_at(value, self.width_f64() / 2.0, self.height_f64() / 2.0);
+	}
+
+	/// Cell in the middle of the view.
+	pub fn view_center(&self) -> (f64, f64) {
+		let pixel_size = self.pixel_size as f64;
+
+		(
+			(self.canvas_drag_x + self.width_f64() / 2.0) / pixel_size,
+			(self.canvas_drag_y + self.height_f64() / 2.0) / pixel_size
+		)
+	}
+
+	/// Moves the view so the cell is in the middle of it.
+	pub fn set_view(&mut self, (center_x, center_y): (f64, f64), pixel_size: usize) {
+		self.pixel_size = pixel_size.max(MIN_PIXEL_SIZE);
+
+		let pixel_size = self.pixel_size as f64;
+
+		self.set_drag_pos(
+			center_x * pixel_size - self.width_f64() / 2.0,
+			center_y * pixel_size - self.height_f64() / 2.0
+		);
+	}
+
+	/// Zooms and moves the view so every cell between the two corners is in it.
+	pub fn zoom_to_cells(&mut self, min: CellPos, max: CellPos) {
+		let cells_x = (max.0 - min.0 + 1 + FIT_PADDING * 2) as f64;
+		let cells_y = (max.1 - min.1 + 1 + FIT_PADDING * 2) as f64;
+
+		let pixel_size = (self.width_f64() / cells_x).min(self.height_f64() / cells_y).floor() as usize;
+
+		self.set_view(
+			(
+				f64::from(min.0 + max.0 + 1) / 2.0,
+				f64::from(min.1 + max.1 + 1) / 2.0
+			),
+			pixel_size.clamp(MIN_PIXEL_SIZE, MAX_FIT_PIXEL_SIZE)
+		);
+	}
+
 	pub fn is_last_button_press_right(&self) -> bool {
 		self.button_pressed.map(MouseButton::right).unwrap_or_default()
 	}
@@ -201,15 +266,16 @@ impl ViewOptions {
 
 	// Other
 
-	pub fn cells_in_view(&self) -> (usize, usize, usize, usize) {
+	/// First cell in view and how many cells fit across and down.
+	pub fn cells_in_view(&self) -> (i32, i32, usize, usize) {
 		let drag_x = self.get_canvas_drag_x();
 		let drag_y = self.get_canvas_drag_y();
 
 		let pixel_size = self.pixel_size as f64;
 
 		(
-			(drag_x / pixel_size).floor() as usize,
-			(drag_y / pixel_size).floor() as usize,
+			(drag_x / pixel_size).floor() as i32,
+			(drag_y / pixel_size).floor() as i32,
 			(self.canvas_width as f64 / pixel_size).ceil() as usize + 1,
 			(self.canvas_height as f64 / pixel_size).ceil() as usize + 1
 		)
@@ -230,11 +296,6 @@ pub struct Cursor {
 impl Cursor {
 	// Mouse
 
-	pub fn is_cells_positive(&self) -> bool {
-		self.cell_x().is_positive() &&
-		self.cell_y().is_positive()
-	}
-
 	pub fn mouse_fixed_x(&self) -> f64 {
 		self.cursor_pos_x
 	}
@@ -259,18 +320,10 @@ impl Cursor {
 		(self.mouse_y() / self.pixel_size as f64).floor() as i32
 	}
 
-	pub fn cell(&self) -> (i32, i32) {
+	pub fn cell(&self) -> CellPos {
 		(self.cell_x(), self.cell_y())
 	}
 
-	pub fn cell_usize_unchecked(&self) -> CellPos {
-		(self.cell_x() as usize, self.cell_y() as usize)
-	}
-
-	pub fn cell_usize_checked(&self) -> CellPos {
-		(self.cell_x().max(0) as usize, self.cell_y().max(0) as usize)
-	}
-
 	// Dragging
 
 	pub fn drag_x(&self) -> f64 {
@@ -87,6 +87,20 @@ pub fn body() -> HtmlElement {
 	document().body().expect("Unwrapping Body")
 }
 
+/// Makes the browser download `contents` as a text file.
+pub fn download_file(file_name: &str, contents: &str) -> Result<()> {
+	let link = create_element::<HtmlElement>("a");
+
+	link.set_attribute("href", &format!("data:text/plain;charset=utf-8,{}", js_sys::encode_uri_component(contents)))?;
+	link.set_attribute("download", file_name)?;
+
+	body().append_with_node_1(&link)?;
+	link.click();
+	link.remove();
+
+	Ok(())
+}
+
 
 
 #[wasm_bindgen(start)]
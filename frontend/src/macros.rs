@@ -39,4 +39,31 @@ macro_rules! register_tickable {
 			}
 		}
 	};
+}
+
+#[macro_export]
+macro_rules! register_editable {
+	($ty:ty) => {
+		impl $crate::objects::ImplEditable for $ty {
+			fn as_editable_ref(&self) -> Option<&dyn $crate::objects::Editable> {
+				Some(self)
+			}
+
+			fn as_editable_mut(&mut self) -> Option<&mut dyn $crate::objects::Editable> {
+				Some(self)
+			}
+		}
+	};
+
+	(!$ty:ty) => {
+		impl $crate::objects::ImplEditable for $ty {
+			fn as_editable_ref(&self) -> Option<&dyn $crate::objects::Editable> {
+				None
+			}
+
+			fn as_editable_mut(&mut self) -> Option<&mut dyn $crate::objects::Editable> {
+				None
+			}
+		}
+	};
 }
\ No newline at end of file
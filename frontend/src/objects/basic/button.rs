@@ -76,3 +76,4 @@ impl Tickable for ButtonState {
 
 
 register_tickable!(ButtonState);
+register_editable!(!ButtonState);
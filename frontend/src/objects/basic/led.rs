@@ -138,4 +138,5 @@ impl Tickable for LedState {
     }
 }
 
-register_tickable!(LedState);
\ No newline at end of file
+register_tickable!(LedState);
+register_editable!(!LedState);
\ No newline at end of file
@@ -3,7 +3,7 @@ use super::Renderable;
 
 use crate::{
 	canvas::{PixelMap, PixelColor, PixelType},
-	objects::{ObjectData, Dimensions}
+	objects::{ObjectData, Dimensions, Property, PropertyValue}
 };
 use crate::Result;
 
@@ -21,7 +21,7 @@ impl Creatable for SwitchState {
 	fn new_with_opts(id: ObjectId, position: CellPos, dimensions: Option<Dimensions>) -> Box<dyn Renderable> {
 		Box::new(
 			Self {
-				state: ObjectState::new_opts(id, ObjectType::Switch, position, dimensions.unwrap_or_else(Self::default_dim), false),
+				state: ObjectState::new_opts(id, ObjectType::Switch, position, dimensions.unwrap_or_else(Self::default_dim), serde_json::json!({ "active": false })),
 				active: false
 			}
 		)
@@ -74,6 +74,26 @@ impl Renderable for SwitchState {
 		String::from(if self.is_toggled() { "1" } else { "0" })
 	}
 
+	fn properties(&self) -> Vec<Property> {
+		let mut properties = self.state.common_properties();
+
+		properties.push(Property::new("active", "On by Default", PropertyValue::Bool(self.state.get_setting("active").unwrap_or_default())));
+
+		properties
+	}
+
+	fn set_property(&mut self, key: &str, value: PropertyValue) -> bool {
+		match (key, value) {
+			("active", PropertyValue::Bool(value)) => {
+				self.state.set_setting("active", value);
+				self.active = value;
+				true
+			}
+
+			(key, value) => self.state.set_common_property(key, value)
+		}
+	}
+
 	fn get_object_state(&self) -> &ObjectState {
 		&self.state
 	}
@@ -83,7 +103,7 @@ impl Renderable for SwitchState {
 	}
 
 	fn reset_values(&mut self) -> Result<()> {
-		self.active = self.state.get_default_saved();
+		self.active = self.state.get_setting("active").unwrap_or_default();
 
 		Ok(())
 	}
@@ -119,4 +139,5 @@ impl Tickable for SwitchState {
 }
 
 
-register_tickable!(SwitchState);
\ No newline at end of file
+register_tickable!(SwitchState);
+register_editable!(!SwitchState);
\ No newline at end of file
@@ -0,0 +1,145 @@
+use serde_json::Value as JsonValue;
+
+use super::{CellPos, Dimensions, Editable, Node, NodeObjectSide, NodeValue, NodeValueTypes, ObjectData, ObjectId, ObjectState, ObjectType, ObjectUpdateEvent};
+use super::Renderable;
+
+use crate::{Result, canvas::PixelMap};
+
+
+// AND, OR, XOR and NOT.
+// Inputs are latched as they're received and the output is sent on the next Global Tick.
+
+pub struct LogicGateState {
+	state: ObjectState,
+
+	/// Inputs which received an active value since the last Global Tick.
+	received: Vec<NodeObjectSide>,
+
+	last_value: NodeValue
+}
+
+impl LogicGateState {
+	pub fn new_with_opts(type_of: ObjectType, id: ObjectId, position: CellPos, dimensions: Option<Dimensions>) -> Box<dyn Renderable> {
+		Box::new(Self {
+			state: ObjectState::new_opts(id, type_of, position, dimensions.unwrap_or_else(|| type_of.default_dimensions()), JsonValue::Null),
+			received: Vec::new(),
+			last_value: NodeValue::Gpio(false)
+		})
+	}
+
+	pub fn new_with_nodes(type_of: ObjectType, id: ObjectId, position: CellPos, dimensions: Option<Dimensions>) -> Box<dyn Renderable> {
+		let mut item = Self::new_with_opts(type_of, id, position, dimensions);
+
+		for (side, direction, accepts) in type_of.default_nodes() {
+			item.add_node(Node::new(side, direction, accepts));
+		}
+
+		item
+	}
+
+	fn evaluate(&self) -> bool {
+		let inputs: Vec<bool> = self.state.nodes.iter()
+			.filter(|n| n.direction.is_input())
+			.map(|n| self.received.contains(&n.side))
+			.collect();
+
+		self.state.type_of.gate_output(&inputs).unwrap_or_default()
+	}
+}
+
+
+impl Renderable for LogicGateState {
+	fn pixel_map(&self) -> PixelMap {
+		PixelMap::new_with_nodes(self.state.get_dimensions(), &self.state.nodes)
+	}
+
+	fn name(&self) -> String {
+		format!("{:?}", self.state.type_of)
+	}
+
+	fn display_name(&self) -> String {
+		String::from(match self.state.type_of {
+			ObjectType::AndGate => "AND",
+			ObjectType::OrGate => "OR",
+			ObjectType::XorGate => "XOR",
+			ObjectType::NotGate => "NOT",
+			_ => "?"
+		})
+	}
+
+	fn get_object_state(&self) -> &ObjectState {
+		&self.state
+	}
+
+	fn get_object_state_mut(&mut self) -> &mut ObjectState {
+		&mut self.state
+	}
+
+	fn current_value(&self) -> NodeValue {
+		self.last_value
+	}
+
+	fn on_receive(&mut self, from_node: NodeObjectSide, value: NodeValue) -> Result<Option<Vec<ObjectData>>> {
+		if value.is_active() && !self.received.contains(&from_node) {
+			self.received.push(from_node);
+		}
+
+		Ok(None)
+	}
+
+	fn reset_values(&mut self) -> Result<()> {
+		self.received.clear();
+		self.last_value = NodeValue::Gpio(false);
+
+		Ok(())
+	}
+}
+
+
+impl Tickable for LogicGateState {
+	fn tick(&mut self, event: ObjectUpdateEvent) -> Result<Vec<ObjectData>> {
+		if !event.is_global_tick() {
+			return Ok(Vec::new());
+		}
+
+		let value = self.evaluate();
+
+		self.received.clear();
+		self.last_value = NodeValue::Gpio(value);
+
+		if value {
+			Ok(
+				self.state.nodes.iter()
+					.filter(|n| n.direction.is_output())
+					.map(|n| n.send_output(self.get_id(), self.last_value))
+					.collect()
+			)
+		} else {
+			Ok(Vec::new())
+		}
+	}
+}
+
+
+impl Editable for LogicGateState {
+	fn min_dimensions(&self) -> Option<Dimensions> {
+		Some(Dimensions(2, 2))
+	}
+
+	fn max_dimensions(&self) -> Option<Dimensions> {
+		Some(Dimensions(8, 16))
+	}
+
+	/// NOT only has a single input.
+	fn are_nodes_editable(&self) -> bool {
+		self.state.type_of != ObjectType::NotGate
+	}
+
+	fn editable_values(&self) -> Vec<NodeValueTypes> {
+		vec![NodeValueTypes::Gpio]
+	}
+}
+
+
+register_tickable!(LogicGateState);
+register_editable!(LogicGateState);
@@ -17,16 +17,18 @@ use crate::{
 
 
 pub mod basic;
-// pub mod gates;
+pub mod gates;
+pub mod property;
 pub mod testobj;
 
 pub use basic::*;
-// pub use gates::*;
+pub use gates::*;
+pub use property::{Property, PropertyValue};
 pub use testobj::TestObjState;
 
 
 use circuit_sim_common::object::ObjectType;
-use circuit_sim_common::size::{NodeObjectSide, Dimensions};
+use circuit_sim_common::size::{NodeObjectSide, Dimensions, Orientation};
 
 
 
@@ -44,6 +46,8 @@ pub fn create_new_object(id: ObjectId, type_of: ObjectType, position: CellPos, d
 		ObjectType::Button => ButtonState::new_with_nodes(id, position, dimensions),
 		ObjectType::Led => LedState::new_with_nodes(id, position, dimensions),
 
+		v if v.is_gate() => LogicGateState::new_with_nodes(v, id, position, dimensions),
+
 		v => unimplemented!("{:?}", v)
 	}
 }
@@ -57,20 +61,23 @@ pub struct ObjectState {
 
 	cell_pos: CellPos,
 	dimensions: Dimensions,
+	orientation: Orientation,
 
 	pub nodes: Vec<Node>,
 
-	default: JsonValue
+	/// Saved settings of the Object. A JSON object once anything is set.
+	settings: JsonValue
 }
 
 impl ObjectState {
-	pub fn new_opts<D: Into<JsonValue>>(id: ObjectId, type_of: ObjectType, cell_pos: CellPos, dimensions: Dimensions, default: D) -> Self {
+	pub fn new_opts<D: Into<JsonValue>>(id: ObjectId, type_of: ObjectType, cell_pos: CellPos, dimensions: Dimensions, settings: D) -> Self {
 		Self {
 			id,
 			type_of,
 			cell_pos,
 			dimensions,
-			default: default.into(),
+			orientation: Orientation::default(),
+			settings: settings.into(),
 
 			nodes: Vec::new()
 		}
@@ -81,8 +88,83 @@ impl ObjectState {
 	}
 
 
-	pub fn get_default_saved<D: DeserializeOwned>(&self) -> D {
-		serde_json::from_value(self.default.clone()).unwrap()
+	/// `None` if the setting was never saved or has a different type.
+	pub fn get_setting<D: DeserializeOwned>(&self, key: &str) -> Option<D> {
+		self.settings.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+	}
+
+	pub fn set_setting<V: Into<JsonValue>>(&mut self, key: &str, value: V) {
+		if !self.settings.is_object() {
+			self.settings = JsonValue::Object(serde_json::Map::new());
+		}
+
+		if let Some(settings) = self.settings.as_object_mut() {
+			settings.insert(key.to_string(), value.into());
+		}
+	}
+
+	pub fn get_settings(&self) -> &JsonValue {
+		&self.settings
+	}
+
+	/// Saved settings override the ones the Object was created with. Missing ones are kept.
+	pub fn merge_settings(&mut self, settings: JsonValue) {
+		match settings {
+			JsonValue::Object(settings) => {
+				for (key, value) in settings {
+					self.set_setting(&key, value);
+				}
+			}
+
+			JsonValue::Null => (),
+
+			settings => self.settings = settings
+		}
+	}
+
+	/// Name given in the properties panel.
+	pub fn get_name(&self) -> Option<String> {
+		self.get_setting::<String>("name").filter(|v| !v.is_empty())
+	}
+
+	/// Name and Node labels. Every Object has them.
+	pub fn common_properties(&self) -> Vec<Property> {
+		let mut properties = vec![
+			Property::new("name", "Name", PropertyValue::Text(self.get_name().unwrap_or_default()))
+		];
+
+		for (index, node) in self.nodes.iter().enumerate() {
+			properties.push(Property::new(
+				format!("node.{index}"),
+				format!("{} {} Label", if node.direction.is_input() { "Input" } else { "Output" }, index + 1),
+				PropertyValue::Text(node.label.clone().unwrap_or_default())
+			));
+		}
+
+		properties
+	}
+
+	/// Applies one of `common_properties`. Returns false if it isn't one of them.
+	pub fn set_common_property(&mut self, key: &str, value: PropertyValue) -> bool {
+		let PropertyValue::Text(value) = value else { return false };
+
+		if key == "name" {
+			self.set_setting("name", value);
+			return true;
+		}
+
+		let node = key.strip_prefix("node.")
+			.and_then(|v| v.parse::<usize>().ok())
+			.and_then(|index| self.nodes.get_mut(index));
+
+		match node {
+			Some(node) => {
+				node.label = Some(value).filter(|v| !v.is_empty());
+				true
+			}
+
+			None => false
+		}
 	}
 
 
@@ -90,11 +172,131 @@ impl ObjectState {
 		self.dimensions
 	}
 
+	/// Nodes on a side which changed length are spread out evenly along it, keeping their order.
 	pub fn set_dimensions(&mut self, dimensions: Dimensions) {
+		for side in [Side::Top, Side::Right, Side::Bottom, Side::Left] {
+			let length = dimensions.from_side(side);
+
+			if length == self.dimensions.from_side(side) {
+				continue;
+			}
+
+			let mut on_side: Vec<&mut Node> = self.nodes.iter_mut().filter(|n| n.side == side).collect();
+			on_side.sort_by_key(|n| n.side.value());
+
+			let count = on_side.len();
+
+			for (i, node) in on_side.into_iter().enumerate() {
+				let index = ((2 * i + 1) * length / (2 * count)).min(length.saturating_sub(1));
+				node.side = node.side.at(index);
+			}
+		}
+
 		self.dimensions = dimensions;
 		self.update_nodes();
 	}
 
+	/// Does every side have room for its Nodes at these dimensions.
+	pub fn can_fit_nodes(&self, dimensions: Dimensions) -> bool {
+		[Side::Top, Side::Right, Side::Bottom, Side::Left]
+			.iter()
+			.all(|&side| self.nodes.iter().filter(|n| n.side == side).count() <= dimensions.from_side(side))
+	}
+
+	/// Node slot on the outline of the Object at the cell. Corners aren't slots.
+	pub fn side_at_cell(&self, (x, y): CellPos) -> Option<NodeObjectSide> {
+		let (pos_x, pos_y) = self.cell_pos;
+		let (width, height) = (self.dimensions.width() as i32, self.dimensions.height() as i32);
+
+		let in_x = x >= pos_x && x < pos_x + width;
+		let in_y = y >= pos_y && y < pos_y + height;
+
+		if in_y && x + 1 == pos_x {
+			Some(NodeObjectSide::Left((pos_y + height - 1 - y) as usize))
+		} else if in_y && x == pos_x + width {
+			Some(NodeObjectSide::Right((y - pos_y) as usize))
+		} else if in_x && y + 1 == pos_y {
+			Some(NodeObjectSide::Top((x - pos_x) as usize))
+		} else if in_x && y == pos_y + height {
+			Some(NodeObjectSide::Bottom((pos_x + width - 1 - x) as usize))
+		} else {
+			None
+		}
+	}
+
+	pub fn node_index_at_side(&self, side: NodeObjectSide) -> Option<usize> {
+		self.nodes.iter().position(|n| n.side == side)
+	}
+
+	pub fn insert_node(&mut self, node: Node) {
+		self.nodes.push(node);
+		self.update_nodes();
+	}
+
+	pub fn remove_node(&mut self, index: usize) {
+		self.nodes.remove(index);
+	}
+
+	pub fn move_node(&mut self, index: usize, side: NodeObjectSide) {
+		self.nodes[index].side = side;
+		self.update_nodes();
+	}
+
+	pub fn flip_node_direction(&mut self, index: usize) {
+		let node = &mut self.nodes[index];
+
+		node.direction = if node.direction.is_input() {
+			NodeDirection::Output
+		} else {
+			NodeDirection::Input
+		};
+	}
+
+	pub fn get_orientation(&self) -> Orientation {
+		self.orientation
+	}
+
+	pub fn set_orientation(&mut self, orientation: Orientation) {
+		self.orientation = orientation;
+	}
+
+	/// Rotates 90° around the top left cell. Nodes keep their place along the outline.
+	pub fn rotate_clockwise(&mut self) {
+		self.dimensions.rotate();
+		self.orientation = self.orientation.rotated_clockwise();
+
+		for node in &mut self.nodes {
+			node.side = node.side.next_side_clockwise();
+		}
+
+		self.update_nodes();
+	}
+
+	/// Rotates 90° around the top left cell. Nodes keep their place along the outline.
+	pub fn rotate_counter_clockwise(&mut self) {
+		self.dimensions.rotate();
+		self.orientation = self.orientation.rotated_counter_clockwise();
+
+		for node in &mut self.nodes {
+			node.side = node.side.next_side_counter_clockwise();
+		}
+
+		self.update_nodes();
+	}
+
+	/// Flips horizontally. Left and Right Nodes swap.
+	pub fn mirror(&mut self) {
+		let dimensions = self.dimensions;
+
+		self.orientation = self.orientation.flipped();
+
+		for node in &mut self.nodes {
+			node.side = node.side.mirrored(&dimensions);
+		}
+
+		self.update_nodes();
+	}
+
 	pub fn get_cell_pos(&self) -> CellPos {
 		self.cell_pos
 	}
@@ -109,20 +311,15 @@ impl ObjectState {
 		let obj_dim = self.dimensions;
 
 		for node in &mut self.nodes {
-			if let Some(pos) = node.side.get_cell_pos(&obj_dim, self.cell_pos) {
-				node.cell_pos = pos;
-				node.is_disabled = false;
-			} else {
-				node.cell_pos = (0, 0);
-				node.is_disabled = true;
-			}
+			node.cell_pos = node.side.get_cell_pos(&obj_dim, self.cell_pos);
+			node.is_disabled = false;
 		}
 	}
 }
 
 
 
-pub trait Renderable: ImplTickable {
+pub trait Renderable: ImplTickable + ImplEditable {
 	fn get_object_state(&self) -> &ObjectState;
 	fn get_object_state_mut(&mut self) -> &mut ObjectState;
 
@@ -155,6 +352,16 @@ pub trait Renderable: ImplTickable {
 	fn name(&self) -> String;
 	fn display_name(&self) -> String;
 
+	/// Settings shown in the properties panel.
+	fn properties(&self) -> Vec<Property> {
+		self.get_object_state().common_properties()
+	}
+
+	/// Applies a setting changed in the properties panel. Returns false if it isn't one of `properties`.
+	fn set_property(&mut self, key: &str, value: PropertyValue) -> bool {
+		self.get_object_state_mut().set_common_property(key, value)
+	}
+
 	fn on_receive(&mut self, _node_side: NodeObjectSide, _value: NodeValue) -> Result<Option<Vec<ObjectData>>> {
 		Ok(None)
 	}
@@ -165,6 +372,18 @@ pub trait Renderable: ImplTickable {
 
 	fn reset_values(&mut self) -> Result<()> { Ok(()) }
 
+	/// Settings written into the saved Canvas.
+	fn export_settings(&self) -> JsonValue {
+		self.get_object_state().get_settings().clone()
+	}
+
+	/// Applies the settings of a loaded Object.
+	fn import_settings(&mut self, settings: JsonValue) -> Result<()> {
+		self.get_object_state_mut().merge_settings(settings);
+
+		self.reset_values()
+	}
+
 	/// Rendering of the `Object`.
 	fn render(
 		&self,
@@ -191,12 +410,32 @@ pub trait Renderable: ImplTickable {
 			ctx.set_fill_style(&JsValue::from_str("#a6a6a6"));
 
 			ctx.fill_text(
-				&self.display_name(),
+				&self.get_object_state().get_name().unwrap_or_else(|| self.display_name()),
 				obj_pos.0 + (width / 2.0) + 2.0,
 				obj_pos.1 + (height / 2.0) + 3.0
 			)?;
 		}
 
+		// Node Labels. Displayed above their node.
+		if view.pixel_size > 15 {
+			ctx.set_text_align("center");
+			ctx.set_text_baseline("bottom");
+
+			ctx.set_font("11px Verdana");
+
+			ctx.set_fill_style(&JsValue::from_str("#d6d6d6"));
+
+			for node in self.get_object_state().nodes.iter().filter(|n| !n.is_disabled) {
+				if let Some(label) = node.label.as_deref() {
+					ctx.fill_text(
+						label,
+						node.cell_pos.0 as f64 * cell_size + (cell_size / 2.0),
+						node.cell_pos.1 as f64 * cell_size - 2.0
+					)?;
+				}
+			}
+		}
+
 		Ok(())
 	}
 
@@ -220,7 +459,7 @@ pub trait Renderable: ImplTickable {
 		let dim = self.get_object_state().dimensions;
 
 		let (min_x, min_y) = self.get_object_state().cell_pos;
-		let (max_x, max_y) = (min_x + dim.width() - 1, min_y + dim.height() - 1);
+		let (max_x, max_y) = (min_x + dim.width() as i32 - 1, min_y + dim.height() as i32 - 1);
 
 		let (p_x, p_y) = value;
 
@@ -228,12 +467,12 @@ pub trait Renderable: ImplTickable {
 		(p_x >= min_x && p_y >= min_y && p_x <= max_x && p_y <= max_y)
 		|| self.get_object_state().nodes
 			.iter()
-			.any(|n| n.side.get_cell_pos(&dim, (min_x, min_y)) == Some(value))
+			.any(|n| n.side.get_cell_pos(&dim, (min_x, min_y)) == value)
 	}
 
 	// Rename to is_point_inside or something of the sort.
 	fn is_mouse_inside(&self, mouse: &Cursor) -> bool {
-		mouse.is_cells_positive() && self.is_cell_inside(mouse.cell_usize_unchecked())
+		self.is_cell_inside(mouse.cell())
 	}
 
 	fn add_node(&mut self, node: Node) {
@@ -273,12 +512,27 @@ pub trait Creatable {
 // Ideas below from https://stackoverflow.com/a/30275713
 
 /// If the Object can be resized, nodes remapped, ...
-// pub trait Editable {
-// 	fn max_dimensions(&self) -> Option<Dimensions> { None }
-// 	fn min_dimensions(&self) -> Option<Dimensions> { None }
+pub trait Editable {
+	fn max_dimensions(&self) -> Option<Dimensions> { None }
+	fn min_dimensions(&self) -> Option<Dimensions> { None }
 
-// 	fn editable_values(&self) -> Vec<NodeValueTypes>;
-// }
+	/// Can Nodes be added, removed, moved or flipped.
+	fn are_nodes_editable(&self) -> bool { true }
+
+	/// Values new Nodes accept. The first one is used.
+	fn editable_values(&self) -> Vec<NodeValueTypes>;
+
+	/// Dimensions clamped between the min and max dimensions.
+	fn clamp_dimensions(&self, dimensions: Dimensions) -> Dimensions {
+		let min = self.min_dimensions().unwrap_or(Dimensions(1, 1));
+		let max = self.max_dimensions().unwrap_or(Dimensions(usize::MAX, usize::MAX));
+
+		Dimensions::checked(
+			dimensions.width().clamp(min.width(), max.width()),
+			dimensions.height().clamp(min.height(), max.height())
+		)
+	}
+}
 
 // pub fn default_editable_nodes() -> Vec<NodeType> {
 // 	vec![NodeType::Input, NodeType::Output]
@@ -296,10 +550,10 @@ pub trait Tickable {
 }
 
 
-// pub trait ImplEditable {
-// 	fn as_editable_ref(&self) -> Option<&dyn Editable>;
-// 	fn as_editable_mut(&mut self) -> Option<&mut dyn Editable>;
-// }
+pub trait ImplEditable {
+	fn as_editable_ref(&self) -> Option<&dyn Editable>;
+	fn as_editable_mut(&mut self) -> Option<&mut dyn Editable>;
+}
 
 pub trait ImplTickable {
 	fn as_tickable_ref(&self) -> Option<&dyn Tickable>;
@@ -349,6 +603,8 @@ impl ObjectData {
 
 							match &mut pixel.type_of {
 								PixelType::Node { direction: NodeDirection::Input, .. } => {
+									canvas.signals.receive(*cell_pos_move, node_value);
+
 									let into_obj_id = canvas.pixels.get_object_in_cell_mut(*cell_pos_move)
 										.map(|object| {
 											if let Ok(Some(mut value)) = object.on_receive(node_side, node_value) {
@@ -364,6 +620,8 @@ impl ObjectData {
 								}
 
 								PixelType::Wire { value, .. } => {
+									canvas.signals.send(*cell_pos_move, node_value);
+
 									if &node_value != value {
 										*value = node_value;
 										data.push(ObjectData::Wire(moving.opposite(), *cell_pos_move));
@@ -389,6 +647,8 @@ impl ObjectData {
 
 							match &mut pixel.type_of {
 								PixelType::Node { direction: NodeDirection::Input, .. } => {
+									canvas.signals.receive(*cell_pos_move, start_value);
+
 									let obj_id = canvas.pixels
 										.get_object_in_cell_mut(*cell_pos_move)
 										.and_then(|object| {
@@ -409,9 +669,13 @@ impl ObjectData {
 								PixelType::Wire { index, value } => {
 									let new_wire_pixel = palette[*index].0;
 
-									if &start_value != value && start_pixel == new_wire_pixel {
-										*value = start_value;
-										data.push(ObjectData::Wire(moving.opposite(), *cell_pos_move));
+									if start_pixel == new_wire_pixel {
+										canvas.signals.send(*cell_pos_move, start_value);
+
+										if &start_value != value {
+											*value = start_value;
+											data.push(ObjectData::Wire(moving.opposite(), *cell_pos_move));
+										}
 									}
 								}
 
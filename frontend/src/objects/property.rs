@@ -0,0 +1,32 @@
+/// Value of a setting edited in the properties panel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+	Bool(bool),
+	/// Whole number between `min` and `max`. Both inclusive.
+	Number {
+		value: i64,
+		min: i64,
+		max: i64
+	},
+	Text(String)
+}
+
+
+/// A setting an Object shows in the properties panel.
+#[derive(Debug, Clone)]
+pub struct Property {
+	/// Passed back into `Renderable::set_property` once it's changed.
+	pub key: String,
+	pub title: String,
+	pub value: PropertyValue
+}
+
+impl Property {
+	pub fn new<K: Into<String>, T: Into<String>>(key: K, title: T, value: PropertyValue) -> Self {
+		Self {
+			key: key.into(),
+			title: title.into(),
+			value
+		}
+	}
+}
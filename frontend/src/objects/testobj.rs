@@ -2,7 +2,7 @@ use circuit_sim_common::object::NodeValue;
 use serde_json::Value as JsonValue;
 
 use crate::{Result, canvas::PixelMap, editor::Node};
-use super::{CellPos, Creatable, NodeObjectSide, NodeValueTypes, ObjectId, ObjectState, ObjectType};
+use super::{CellPos, Creatable, Editable, NodeObjectSide, NodeValueTypes, ObjectId, ObjectState, ObjectType};
 use super::Renderable;
 use crate::objects::{ObjectData, Dimensions};
 
@@ -77,4 +77,12 @@ impl Renderable for TestObjState {
 }
 
 
-register_tickable!(!TestObjState);
\ No newline at end of file
+impl Editable for TestObjState {
+	fn editable_values(&self) -> Vec<NodeValueTypes> {
+		vec![NodeValueTypes::Gpio]
+	}
+}
+
+
+register_tickable!(!TestObjState);
+register_editable!(TestObjState);
\ No newline at end of file
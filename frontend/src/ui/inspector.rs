@@ -0,0 +1,186 @@
+use std::cell::RefCell;
+
+use web_sys::{HtmlCanvasElement, HtmlDivElement, HtmlSpanElement, Node as HtmlNode};
+
+use circuit_sim_common::{CellPos, netlist::Netlist};
+
+use crate::{
+	Result,
+	canvas::PixelType,
+	editor::{CanvasState, ViewOptions, net},
+	statics::get_editor_state
+};
+
+
+/// Distance between the cursor and the tooltip in pixels.
+const CURSOR_OFFSET: f64 = 16.0;
+
+
+/// History revision, amount of cells and amount of Objects the netlist was built for.
+type NetlistKey = (usize, usize, usize);
+
+
+/// Tooltip describing the cell under the cursor.
+pub struct HoverInspector {
+	element: HtmlDivElement,
+
+	/// Rows which are shown. (Title, Value)
+	rendered: RefCell<Vec<(String, String)>>,
+
+	/// Built again once the Canvas was edited or a different one was loaded.
+	netlist: RefCell<Option<(NetlistKey, Netlist)>>
+}
+
+impl HoverInspector {
+	pub fn new() -> Self {
+		let element = crate::create_element::<HtmlDivElement>("div");
+		element.set_class_name("hover-inspector");
+
+		Self {
+			element,
+			rendered: RefCell::new(Vec::new()),
+			netlist: RefCell::new(None)
+		}
+	}
+
+	pub fn is_shown(&self) -> bool {
+		self.element.class_list().contains("shown")
+	}
+
+	pub fn hide(&self) {
+		let _ = self.element.class_list().remove_1("shown");
+	}
+
+	/// Hides it and forgets the netlist. Used once a different document is shown.
+	pub fn reset(&self) {
+		self.hide();
+		self.netlist.replace(None);
+	}
+
+	/// Describes the cell under the cursor. Hidden if it's empty.
+	pub fn update(&self, view: &ViewOptions, canvas: &HtmlCanvasElement) -> Result<()> {
+		let cursor = view.cursor();
+
+		let rows = match get_editor_state().get_canvas_state() {
+			Some(state) => self.describe_cell(state, cursor.cell()),
+			None => Vec::new()
+		};
+
+		if rows.is_empty() {
+			self.hide();
+			return Ok(());
+		}
+
+		if *self.rendered.borrow() != rows {
+			self.element.set_inner_html("");
+
+			for (title, value) in &rows {
+				let row = crate::create_element::<HtmlDivElement>("div");
+				row.set_class_name("row");
+				self.element.append_with_node_1(&row)?;
+
+				let span_title = crate::create_element::<HtmlSpanElement>("span");
+				span_title.set_class_name("title");
+				span_title.set_inner_text(title);
+				row.append_with_node_1(&span_title)?;
+
+				let span_value = crate::create_element::<HtmlSpanElement>("span");
+				span_value.set_inner_text(value);
+				row.append_with_node_1(&span_value)?;
+			}
+
+			self.rendered.replace(rows);
+		}
+
+		let style = self.element.style();
+		style.set_property("left", &format!("{}px", canvas.offset_left() as f64 + cursor.mouse_fixed_x() + CURSOR_OFFSET))?;
+		style.set_property("top", &format!("{}px", canvas.offset_top() as f64 + cursor.mouse_fixed_y() + CURSOR_OFFSET))?;
+
+		self.element.class_list().add_1("shown")?;
+
+		Ok(())
+	}
+
+	fn describe_cell(&self, state: &CanvasState, cell: CellPos) -> Vec<(String, String)> {
+		let pixels = &state.pixels;
+
+		let Some(pixel) = pixels.get_pixel_type_from_cell(&cell) else { return Vec::new() };
+
+		let mut rows = vec![(String::from("Cell"), format!("({}, {})", cell.0, cell.1))];
+
+		let mut value = None;
+		let mut net_id = None;
+
+		match &pixel.type_of {
+			&PixelType::Wire { index, value: wire_value } => {
+				rows.push((String::from("Pixel"), String::from("Wire")));
+				rows.push((String::from("Palette"), index.to_string()));
+
+				value = Some(wire_value);
+				net_id = self.with_netlist(state, |netlist| netlist.net_of_wire(cell));
+			}
+
+			PixelType::Node { .. } => rows.push((String::from("Pixel"), String::from("Node"))),
+			PixelType::Custom(_) | PixelType::ObjectColor => rows.push((String::from("Pixel"), String::from("Object")))
+		}
+
+		if let Some(object) = pixels.get_object_in_cell(cell) {
+			rows.push((String::from("Object"), format!("{} #{}", object.get_object_state().get_name().unwrap_or_else(|| object.name()), object.get_id().0)));
+
+			if let Some(node) = pixels.get_node_in_cell(cell) {
+				rows.push((String::from("Node"), format!("{:?} {:?}", node.side, node.direction)));
+				rows.push((String::from("Accepts"), format!("{:?}", node.accepts)));
+
+				value = if node.direction.is_output() {
+					Some(object.current_value())
+				} else {
+					state.signals.received_value(cell)
+				};
+
+				let object_index = pixels.objects().iter().position(|o| o.get_id() == object.get_id());
+				let node_index = object.get_object_state().nodes.iter().position(|n| n.cell_pos == cell);
+
+				if let (Some(object_index), Some(node_index)) = (object_index, node_index) {
+					net_id = self.with_netlist(state, |netlist| netlist.net_of_node((object_index, node_index)));
+				}
+			}
+		}
+
+		if let Some(value) = value {
+			rows.push((String::from("Value"), format!("{value:?}")));
+		}
+
+		if let Some(net_id) = net_id {
+			let node_count = self.with_netlist(state, |netlist| netlist.nets[net_id].nodes.len());
+
+			rows.push((String::from("Net"), format!("#{net_id}")));
+			rows.push((String::from("Nodes on Net"), node_count.to_string()));
+		}
+
+		rows
+	}
+
+	fn with_netlist<R, F: FnOnce(&Netlist) -> R>(&self, state: &CanvasState, f: F) -> R {
+		let key = (state.history.revision(), state.pixels.cells.len(), state.pixels.objects().len());
+
+		let mut cached = self.netlist.borrow_mut();
+
+		let (_, netlist) = match cached.take() {
+			Some(value) if value.0 == key => cached.insert(value),
+
+			_ => {
+				let netlist = net::netlist(&state.pixels, &net::object_nodes(&state.pixels));
+
+				cached.insert((key, netlist))
+			}
+		};
+
+		f(netlist)
+	}
+}
+
+impl AsRef<HtmlNode> for HoverInspector {
+	fn as_ref(&self) -> &HtmlNode {
+		&self.element
+	}
+}
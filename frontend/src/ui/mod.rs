@@ -11,13 +11,20 @@ use crate::{Editor, window};
 mod sidebar;
 mod topbar;
 pub mod notification;
+pub mod inspector;
+pub mod net;
+pub mod popup;
+pub mod shortcuts;
 
 use sidebar::Sidebar;
 use topbar::Topbar;
 
 pub use notification::{NotificationType, Notification};
+pub use inspector::HoverInspector;
 
 pub use sidebar::ItemContainer;
+pub use net::net_popup;
+pub use shortcuts::shortcuts_popup;
 
 #[derive(Clone)]
 pub struct MainUi(Rc<RwLock<InnerUI>>);
@@ -0,0 +1,178 @@
+use web_sys::HtmlSpanElement;
+
+use circuit_sim_common::CellPos;
+
+use crate::{
+	Error, Result,
+	editor::{
+		CanvasState,
+		net,
+		state::canvas::{CanvasEvent, CanvasTool}
+	},
+	statics
+};
+
+use super::popup::Popup;
+
+
+/// Actions for the net selected with `CanvasTool::NetSelect`.
+pub fn net_popup() -> Result<()> {
+	let canvas = selected_canvas()?;
+
+	let Some(CanvasEvent::Net(net)) = canvas.event.as_ref() else { return Ok(()) };
+
+	let current = net::net_index(&canvas.pixels, net);
+
+	let popup = Popup::new("Net")?;
+
+	{ // Summary
+		let row = popup.add_row()?;
+
+		let summary = crate::create_element::<HtmlSpanElement>("span");
+		summary.set_inner_text(&format!("{} wire cell{}. Pick a color to recolor it.", net.len(), if net.len() == 1 { "" } else { "s" }));
+		row.append_with_node_1(&summary)?;
+	}
+
+	{ // Colors
+		let row = popup.add_row()?;
+		row.class_list().add_1("colors")?;
+
+		for (index, (color, _)) in canvas.pixels.palette.iter().enumerate() {
+			if Some(index) == current {
+				continue;
+			}
+
+			let popup_copied = popup.clone();
+
+			let button = popup.add_button(&row, "", move |_| {
+				popup_copied.close();
+
+				if let Err(e) = recolor_selected_net(index, false) {
+					log!("Net Error: {:?}", e);
+				}
+			})?;
+
+			button.set_attribute("style", &format!("background-color: {}", color.get_string_color()))?;
+			button.set_title(&format!("Connection Color {}", index + 1));
+		}
+	}
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Highlight", move |_| {
+			if let Ok(canvas) = selected_canvas() {
+				if let Some(CanvasEvent::Net(net)) = canvas.event.as_ref() {
+					canvas.highlighted_net = net.first().copied();
+				}
+			}
+
+			statics::request_render();
+
+			popup_copied.close();
+		})?;
+
+		if canvas.highlighted_net.is_some() {
+			let popup_copied = popup.clone();
+
+			popup.add_button(&row, "Clear Highlight", move |_| {
+				if let Ok(canvas) = selected_canvas() {
+					canvas.highlighted_net = None;
+				}
+
+				statics::request_render();
+
+				popup_copied.close();
+			})?;
+		}
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Delete", move |_| {
+			if let Ok(canvas) = selected_canvas() {
+				if let Some(CanvasEvent::Net(net)) = canvas.event.take() {
+					canvas.delete_net(&net);
+				}
+
+				canvas.event = Some(CanvasEvent::Tooling(CanvasTool::NetSelect));
+			}
+
+			popup_copied.close();
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Close", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
+}
+
+
+/// Recolors the selected net. Asks first if it would connect to another net of that color.
+fn recolor_selected_net(index: usize, is_confirmed: bool) -> Result<()> {
+	let canvas = selected_canvas()?;
+
+	let Some(CanvasEvent::Net(net)) = canvas.event.as_ref() else { return Ok(()) };
+
+	let merging = net::merging_cells(&canvas.pixels, net, index);
+
+	if !merging.is_empty() && !is_confirmed {
+		return confirm_merge_popup(index, &merging);
+	}
+
+	let net = net.clone();
+
+	canvas.recolor_net(&net, index);
+
+	// Select everything it's connected to now.
+	canvas.event = Some(CanvasEvent::Net(net::flood_net(&canvas.pixels, net[0])));
+
+	Ok(())
+}
+
+fn confirm_merge_popup(index: usize, merging: &[CellPos]) -> Result<()> {
+	let popup = Popup::new("Merge Nets?")?;
+
+	{
+		let row = popup.add_row()?;
+
+		let (x, y) = merging[0];
+
+		let info = crate::create_element::<HtmlSpanElement>("span");
+		info.set_inner_text(&format!(
+			"The new color touches another net of that color at ({x}, {y}){}. Recoloring will connect them into a single net.",
+			if merging.len() > 1 { format!(" and {} other cells", merging.len() - 1) } else { String::new() }
+		));
+		row.append_with_node_1(&info)?;
+	}
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Recolor Anyway", move |_| {
+			popup_copied.close();
+
+			if let Err(e) = recolor_selected_net(index, true) {
+				log!("Net Error: {:?}", e);
+			}
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Cancel", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
+}
+
+
+fn selected_canvas<'a>() -> Result<&'a mut CanvasState> {
+	statics::get_editor_state_mut()
+		.get_canvas_state_mut()
+		.ok_or_else(|| Error::String(String::from("Not editing a Canvas")))
+}
@@ -0,0 +1,78 @@
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{HtmlDivElement, HtmlSpanElement, MouseEvent};
+
+use crate::{Result, body};
+
+
+
+#[derive(Clone)]
+pub struct Popup {
+	pub popup: HtmlDivElement,
+	pub container: HtmlDivElement
+}
+
+impl Popup {
+	/// Creates the Popup and displays it. Clicking outside of it closes it.
+	pub fn new(title: &str) -> Result<Self> {
+		let popup = crate::create_element::<HtmlDivElement>("div");
+		popup.set_class_name("popup");
+		body().append_with_node_1(&popup)?;
+
+		let container = crate::create_element::<HtmlDivElement>("div");
+		container.set_class_name("popup-container");
+		popup.append_with_node_1(&container)?;
+
+		{ // On click outside
+			let popup_copied = popup.clone();
+			let closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+				let is_outside = event.target()
+					.and_then(|t| t.dyn_into::<HtmlDivElement>().ok())
+					.is_some_and(|t| t == popup_copied);
+
+				if is_outside {
+					popup_copied.remove();
+				}
+			}) as Box<dyn FnMut(_)>);
+			popup.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		let this = Self { popup, container };
+
+		{ // Title
+			let row = this.add_row()?;
+
+			let span = crate::create_element::<HtmlSpanElement>("span");
+			span.set_class_name("title");
+			span.set_inner_text(title);
+			row.append_with_node_1(&span)?;
+		}
+
+		Ok(this)
+	}
+
+	pub fn add_row(&self) -> Result<HtmlDivElement> {
+		let row = crate::create_element::<HtmlDivElement>("div");
+		row.set_class_name("row");
+		self.container.append_with_node_1(&row)?;
+
+		Ok(row)
+	}
+
+	pub fn add_button<F>(&self, row: &HtmlDivElement, title: &str, on_click: F) -> Result<HtmlDivElement> where F: FnMut(MouseEvent) + 'static {
+		let button = crate::create_element::<HtmlDivElement>("div");
+		button.set_class_name("button");
+		button.set_inner_text(title);
+		row.append_with_node_1(&button)?;
+
+		let closure = Closure::wrap(Box::new(on_click) as Box<dyn FnMut(_)>);
+		button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+
+		Ok(button)
+	}
+
+	pub fn close(&self) {
+		self.popup.remove();
+	}
+}
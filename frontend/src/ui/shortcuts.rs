@@ -0,0 +1,136 @@
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{Event, HtmlInputElement, HtmlSpanElement, KeyboardEvent as HtmlKeyboardEvent};
+
+use crate::{
+	NotificationType,
+	Result,
+	editor::{
+		KeyboardEvent,
+		keymap::{Chord, Command}
+	},
+	statics::{self, get_keymap}
+};
+
+use super::popup::Popup;
+
+
+/// Cheat sheet of every keyboard shortcut. Focusing a shortcut and pressing a key rebinds it.
+pub fn shortcuts_popup() -> Result<()> {
+	let popup = Popup::new("Keyboard Shortcuts")?;
+	popup.container.class_list().add_1("shortcuts")?;
+
+	{
+		let row = popup.add_row()?;
+
+		let info = crate::create_element::<HtmlSpanElement>("span");
+		info.set_inner_text("Click a shortcut and press the new keys. Escape cancels.");
+		row.append_with_node_1(&info)?;
+	}
+
+	let mut inputs = Vec::new();
+
+	for command in Command::all() {
+		let row = popup.add_row()?;
+		row.class_list().add_1("shortcut")?;
+
+		let name = crate::create_element::<HtmlSpanElement>("span");
+		name.set_inner_text(&command.display_name());
+		row.append_with_node_1(&name)?;
+
+		let input = crate::create_element::<HtmlInputElement>("input");
+		input.set_class_name("chord");
+		input.set_read_only(true);
+		row.append_with_node_1(&input)?;
+
+		inputs.push((command, input));
+	}
+
+	let inputs = Rc::new(inputs);
+
+	update_inputs(&inputs);
+
+	for (command, input) in inputs.iter() {
+		let command = *command;
+
+		{ // Focus
+			let input_copied = input.clone();
+			let closure = Closure::wrap(Box::new(move |_: Event| {
+				input_copied.set_value("Press keys...");
+			}) as Box<dyn FnMut(_)>);
+			input.add_event_listener_with_callback("focus", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		{ // Blur
+			let inputs = inputs.clone();
+			let closure = Closure::wrap(Box::new(move |_: Event| {
+				update_inputs(&inputs);
+			}) as Box<dyn FnMut(_)>);
+			input.add_event_listener_with_callback("blur", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		{ // Key Down
+			let input_copied = input.clone();
+			let closure = Closure::wrap(Box::new(move |event: HtmlKeyboardEvent| {
+				event.prevent_default();
+
+				let event = KeyboardEvent::from(event);
+
+				if event.key != "Escape" {
+					let Some(chord) = Chord::from_event(&event) else { return };
+
+					let keymap = get_keymap();
+					keymap.rebind(command, chord);
+
+					if let Err(e) = keymap.save() {
+						let _ = statics::create_notification("Keyboard Shortcuts", NotificationType::Error(e), 1000 * 5);
+					}
+				}
+
+				let _ = input_copied.blur();
+			}) as Box<dyn FnMut(_)>);
+			input.add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+	}
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let inputs = inputs.clone();
+
+		popup.add_button(&row, "Reset to Defaults", move |_| {
+			let keymap = get_keymap();
+			keymap.reset();
+
+			if let Err(e) = keymap.save() {
+				let _ = statics::create_notification("Keyboard Shortcuts", NotificationType::Error(e), 1000 * 5);
+			}
+
+			update_inputs(&inputs);
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Close", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
+}
+
+
+fn update_inputs(inputs: &[(Command, HtmlInputElement)]) {
+	let keymap = get_keymap();
+
+	for (command, input) in inputs {
+		let chords: Vec<String> = keymap.chords_for(*command)
+			.into_iter()
+			.map(ToString::to_string)
+			.collect();
+
+		input.set_value(&chords.join(" / "));
+	}
+}
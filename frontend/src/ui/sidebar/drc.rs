@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{HtmlDivElement, HtmlInputElement, HtmlLabelElement, HtmlLiElement, HtmlSpanElement, HtmlUListElement, MouseEvent};
+
+use crate::{
+	CellPos, NotificationType, Result, window,
+	editor::drc::{Finding, check_design},
+	statics::{get_editor_state, get_editor_state_mut, is_editor_running, request_render}
+};
+
+use super::ItemContainer;
+
+
+/// How often the continuous check runs.
+const CONTINUOUS_CHECK_EVERY: i32 = 1000;
+
+
+struct DrcPanel {
+	list: HtmlUListElement,
+	summary: HtmlSpanElement,
+
+	findings: RefCell<Vec<Finding>>,
+	selected: RefCell<Option<HtmlLiElement>>
+}
+
+impl DrcPanel {
+	/// Checks the Canvas and re-renders the list if the findings changed or if forced.
+	fn check(self: &Rc<Self>, force: bool) -> Result<()> {
+		let findings = match get_editor_state().get_canvas_state() {
+			Some(canvas) => check_design(&canvas.pixels),
+			None => return Ok(())
+		};
+
+		if !force && *self.findings.borrow() == findings {
+			return Ok(());
+		}
+
+		set_highlighted(Vec::new());
+		self.selected.replace(None);
+
+		self.list.set_inner_html("");
+
+		if findings.is_empty() {
+			self.summary.set_inner_text("No problems found.");
+		} else {
+			self.summary.set_inner_text(&format!("{} problem(s) found.", findings.len()));
+		}
+
+		for finding in &findings {
+			self.render_finding(finding)?;
+		}
+
+		self.findings.replace(findings);
+
+		Ok(())
+	}
+
+	fn render_finding(self: &Rc<Self>, finding: &Finding) -> Result<()> {
+		let list_item = crate::create_element::<HtmlLiElement>("li");
+		list_item.set_class_name("item");
+		self.list.append_with_node_1(&list_item)?;
+
+		let title = crate::create_element::<HtmlSpanElement>("span");
+		title.set_class_name("title");
+		title.set_inner_text(finding.rule.title());
+		list_item.append_with_node_1(&title)?;
+
+		let description = crate::create_element::<HtmlSpanElement>("span");
+		description.set_inner_text(&finding.description);
+		list_item.append_with_node_1(&description)?;
+
+		{ // On click. Highlights the finding.
+			let panel = self.clone();
+			let cells = finding.cells.clone();
+			let list_item_copied = list_item.clone();
+
+			let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+				let previous = panel.selected.replace(None);
+
+				if let Some(item) = previous.as_ref() {
+					let _ = item.class_list().remove_1("selected");
+				}
+
+				// Clicking the selected finding again unselects it.
+				if previous.as_ref() == Some(&list_item_copied) {
+					set_highlighted(Vec::new());
+				} else {
+					let _ = list_item_copied.class_list().add_1("selected");
+					set_highlighted(cells.clone());
+
+					panel.selected.replace(Some(list_item_copied.clone()));
+				}
+			}) as Box<dyn FnMut(_)>);
+			list_item.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		Ok(())
+	}
+}
+
+
+fn set_highlighted(cells: Vec<CellPos>) {
+	if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+		canvas.highlighted = cells;
+	}
+
+	request_render();
+}
+
+
+pub fn create_drc_container(top: HtmlDivElement) -> Result<ItemContainer> {
+	let cont = ItemContainer::new("Design Rules", "design-rules", top)?;
+
+	let controls = crate::create_element::<HtmlDivElement>("div");
+	controls.set_class_name("controls");
+	cont.container_inner.append_with_node_1(&controls)?;
+
+	let button_check = crate::create_element::<HtmlDivElement>("div");
+	button_check.set_class_name("button");
+	button_check.set_inner_text("Check");
+	controls.append_with_node_1(&button_check)?;
+
+	let label = crate::create_element::<HtmlLabelElement>("label");
+	controls.append_with_node_1(&label)?;
+
+	let continuous = crate::create_element::<HtmlInputElement>("input");
+	continuous.set_type("checkbox");
+	label.append_with_node_1(&continuous)?;
+	label.append_with_str_1("Continuous")?;
+
+	let summary = crate::create_element::<HtmlSpanElement>("span");
+	summary.set_class_name("summary");
+	cont.container_inner.append_with_node_1(&summary)?;
+
+	let list = crate::create_element::<HtmlUListElement>("ul");
+	list.set_class_name("item-list");
+	cont.container_inner.append_with_node_1(&list)?;
+
+	let panel = Rc::new(DrcPanel {
+		list,
+		summary,
+		findings: RefCell::new(Vec::new()),
+		selected: RefCell::new(None)
+	});
+
+	{ // On Check click
+		let panel = panel.clone();
+
+		let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+			if let Err(e) = panel.check(true) {
+				log!("Design Rule Check Error: {:?}", e);
+				crate::statics::create_notification("Design Rule Check", NotificationType::Error(e), 1000 * 10).expect("notification");
+			}
+		}) as Box<dyn FnMut(_)>);
+		button_check.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	{ // Continuous Check
+		let closure = Closure::wrap(Box::new(move || {
+			if continuous.checked() && !is_editor_running() {
+				if let Err(e) = panel.check(false) {
+					log!("Design Rule Check Error: {:?}", e);
+				}
+			}
+		}) as Box<dyn FnMut()>);
+		window().set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), CONTINUOUS_CHECK_EVERY)?;
+		closure.forget();
+	}
+
+	Ok(cont)
+}
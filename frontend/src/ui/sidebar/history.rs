@@ -0,0 +1,153 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{HtmlDivElement, HtmlLiElement, HtmlUListElement, MouseEvent};
+
+use crate::{
+	Result, window,
+	statics::{get_editor_state, get_editor_state_mut}
+};
+
+use super::ItemContainer;
+
+
+/// How often the list checks for new steps.
+const REFRESH_EVERY: i32 = 250;
+
+
+struct HistoryPanel {
+	list: HtmlUListElement,
+	button_undo: HtmlDivElement,
+	button_redo: HtmlDivElement,
+
+	/// (Revision, Position) of the History when it was rendered.
+	rendered: Cell<Option<(usize, usize)>>
+}
+
+impl HistoryPanel {
+	/// Re-renders the list if the History changed.
+	fn refresh(self: &Rc<Self>) -> Result<()> {
+		let editor = get_editor_state();
+
+		let history = match editor.get_canvas_state() {
+			Some(canvas) => &canvas.history,
+			None => return Ok(())
+		};
+
+		let current = Some((history.revision(), history.position()));
+
+		if self.rendered.get() == current {
+			return Ok(());
+		}
+
+		self.rendered.set(current);
+
+		self.list.set_inner_html("");
+
+		set_class(&self.button_undo, "disabled", !history.can_undo());
+		set_class(&self.button_redo, "disabled", !history.can_redo());
+
+		self.render_item("Start", 0, history.position())?;
+
+		for (index, step) in history.steps().iter().enumerate() {
+			self.render_item(&step.description(), index + 1, history.position())?;
+		}
+
+		Ok(())
+	}
+
+	/// `position` is the amount of steps which are applied once the item is clicked.
+	fn render_item(self: &Rc<Self>, title: &str, position: usize, current: usize) -> Result<()> {
+		let list_item = crate::create_element::<HtmlLiElement>("li");
+		list_item.set_class_name("item");
+		list_item.set_inner_text(title);
+		self.list.append_with_node_1(&list_item)?;
+
+		set_class(&list_item, "selected", position == current);
+		set_class(&list_item, "undone", position > current);
+
+		{ // On click. Goes back or forward to the step.
+			let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+				if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+					canvas.go_to_history(position);
+				}
+			}) as Box<dyn FnMut(_)>);
+			list_item.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		Ok(())
+	}
+}
+
+
+fn set_class(element: &web_sys::Element, class: &str, value: bool) {
+	let _ = element.class_list().toggle_with_force(class, value);
+}
+
+
+pub fn create_history_container(top: HtmlDivElement) -> Result<ItemContainer> {
+	let cont = ItemContainer::new("History", "history", top)?;
+
+	let controls = crate::create_element::<HtmlDivElement>("div");
+	controls.set_class_name("controls");
+	cont.container_inner.append_with_node_1(&controls)?;
+
+	let button_undo = crate::create_element::<HtmlDivElement>("div");
+	button_undo.set_class_name("button");
+	button_undo.set_inner_text("Undo");
+	button_undo.set_title("Ctrl+Z");
+	controls.append_with_node_1(&button_undo)?;
+
+	let button_redo = crate::create_element::<HtmlDivElement>("div");
+	button_redo.set_class_name("button");
+	button_redo.set_inner_text("Redo");
+	button_redo.set_title("Ctrl+Shift+Z");
+	controls.append_with_node_1(&button_redo)?;
+
+	let list = crate::create_element::<HtmlUListElement>("ul");
+	list.set_class_name("item-list");
+	cont.container_inner.append_with_node_1(&list)?;
+
+	{ // On Undo click
+		let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+			if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+				canvas.undo();
+			}
+		}) as Box<dyn FnMut(_)>);
+		button_undo.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	{ // On Redo click
+		let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+			if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+				canvas.redo();
+			}
+		}) as Box<dyn FnMut(_)>);
+		button_redo.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	let panel = Rc::new(HistoryPanel {
+		list,
+		button_undo,
+		button_redo,
+		rendered: Cell::new(None)
+	});
+
+	panel.refresh()?;
+
+	{ // Refresh
+		let closure = Closure::wrap(Box::new(move || {
+			if let Err(e) = panel.refresh() {
+				log!("History Error: {:?}", e);
+			}
+		}) as Box<dyn FnMut()>);
+		window().set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), REFRESH_EVERY)?;
+		closure.forget();
+	}
+
+	Ok(cont)
+}
@@ -8,6 +8,7 @@ use crate::Result;
 
 
 
+#[derive(Clone)]
 pub struct ItemContainer {
 	// Elements
 	pub container: HtmlDivElement,
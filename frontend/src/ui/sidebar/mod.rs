@@ -3,7 +3,7 @@ use wasm_bindgen::{JsCast, closure::Closure};
 use web_sys::{HtmlDivElement, HtmlElement, HtmlLiElement, HtmlUListElement, MouseEvent};
 
 
-use crate::{Editor, Error, NotificationType, Result, canvas::{
+use crate::{Editor, NotificationType, Result, canvas::{
 		PIXEL_ODD,
 		PixelColor
 	}, editor::{
@@ -12,13 +12,23 @@ use crate::{Editor, Error, NotificationType, Result, canvas::{
 			CanvasEvent,
 			CanvasTool
 		}
-	}, error::DisplayError, objects::create_new_object_from_type, statics::{get_editor_state, get_editor_state_mut}};
+	}, objects::create_new_object_from_type, statics::{get_editor_state, get_editor_state_mut}};
 
+mod drc;
+mod history;
 mod item_container;
+mod properties;
 mod start;
+mod views;
+mod wire_mode;
 
 pub use item_container::ItemContainer;
 use start::StartContainer;
+use drc::create_drc_container;
+use history::create_history_container;
+use properties::create_properties_container;
+use views::create_views_container;
+use wire_mode::create_wire_mode_container;
 
 pub struct Sidebar {
 	pub container: HtmlDivElement,
@@ -30,6 +40,10 @@ pub struct Sidebar {
 	pub item_containers: Vec<ItemContainer>,
 	pub obj_settings: Option<ItemContainer>,
 
+	/// Colors of the Connections panel. Drawn again once a Canvas with a different palette is shown.
+	palette_list: HtmlUListElement,
+	shown_palette: Vec<PixelColor>,
+
 	pub start_container: StartContainer
 }
 
@@ -41,6 +55,8 @@ impl Sidebar {
 		Ok(Self {
 			obj_settings: None,
 			item_containers: Vec::new(),
+			palette_list: crate::create_element::<HtmlUListElement>("ul"),
+			shown_palette: Vec::new(),
 			start_container: StartContainer::new(),
 			container: crate::create_element::<HtmlDivElement>("div"),
 			bottom: crate::create_element::<HtmlDivElement>("div"),
@@ -53,13 +69,43 @@ impl Sidebar {
 		self.item_containers.iter().for_each(|i| { let _ = i.render(); });
 	}
 
+	/// Only creates them once. Every document shares them.
 	pub fn init_item_containers(&mut self) -> Result<()> {
+		if !self.item_containers.is_empty() {
+			return Ok(());
+		}
+
 		self.item_containers.append(&mut vec![
 			create_tools_container(self.top.clone())?,
-			create_pixels_container(self.top.clone())?,
-			create_objects_container(self.top.clone())?
+			create_pixels_container(self.top.clone(), self.palette_list.clone())?,
+			create_wire_mode_container(self.top.clone())?,
+			create_views_container(self.top.clone(), self.editor.clone())?,
+			create_objects_container(self.top.clone())?,
+			create_drc_container(self.top.clone())?,
+			create_history_container(self.top.clone())?
 		]);
 
+		// Only shown while an Object is selected.
+		self.obj_settings = Some(create_properties_container(self.top.clone())?);
+
+		Ok(())
+	}
+
+	/// Shows the palette of the current Canvas in the Connections panel.
+	pub fn update_palette(&mut self) -> Result<()> {
+		let palette: Vec<PixelColor> = match get_editor_state().get_canvas_state() {
+			Some(state) => state.pixels.palette.iter().map(|&(color, _)| color).collect(),
+			None => return Ok(())
+		};
+
+		if palette != self.shown_palette {
+			self.palette_list.set_inner_html("");
+
+			render_palette_items(&self.palette_list, &palette)?;
+
+			self.shown_palette = palette;
+		}
+
 		Ok(())
 	}
 
@@ -90,7 +136,9 @@ pub fn create_tools_container(top: HtmlDivElement) -> Result<ItemContainer> {
 	cont.container_inner.append_with_node_1(&item_list)?;
 
 	let items = vec![
-		(PixelColor(237, 174, 192), CanvasTool::Eraser)
+		(PixelColor(237, 174, 192), CanvasTool::Eraser),
+		(PixelColor(92, 173, 154), CanvasTool::Router),
+		(PixelColor(148, 193, 229), CanvasTool::NetSelect)
 	];
 
 	for (pixel, tool) in items {
@@ -119,7 +167,7 @@ pub fn create_tools_container(top: HtmlDivElement) -> Result<ItemContainer> {
 					let editor = get_editor_state_mut();
 
 					if let Some(state) = editor.as_any_mut().downcast_mut::<CanvasState>() {
-						state.event = Some(CanvasEvent::Tooling(tool));
+						state.select_tool(tool);
 					}
 
 					Ok(())
@@ -140,17 +188,18 @@ pub fn create_tools_container(top: HtmlDivElement) -> Result<ItemContainer> {
 }
 
 
-pub fn create_pixels_container(top: HtmlDivElement) -> Result<ItemContainer> {
+/// The palette is added to `item_list` by `Sidebar::update_palette`.
+pub fn create_pixels_container(top: HtmlDivElement, item_list: HtmlUListElement) -> Result<ItemContainer> {
 	let cont = ItemContainer::new("Connections", "connection-colors", top)?;
 
-	let item_list = crate::create_element::<HtmlUListElement>("ul");
 	item_list.set_class_name("item-list");
 	cont.container_inner.append_with_node_1(&item_list)?;
 
-	let editor = get_editor_state();
-	let editor = editor.get_canvas_state().ok_or_else::<Error, _>(|| DisplayError::InvalidState.into())?;
+	Ok(cont)
+}
 
-	for (pixel_color, _) in editor.pixels.palette.clone() {
+fn render_palette_items(item_list: &HtmlUListElement, palette: &[PixelColor]) -> Result<()> {
+	for (index, &pixel_color) in palette.iter().enumerate() {
 		let list_item = crate::create_element::<HtmlLiElement>("li");
 		list_item.set_class_name("item");
 
@@ -176,7 +225,7 @@ pub fn create_pixels_container(top: HtmlDivElement) -> Result<ItemContainer> {
 					let editor = get_editor_state_mut();
 
 					if let Some(state) = editor.as_any_mut().downcast_mut::<CanvasState>() {
-						state.event = Some(CanvasEvent::Painting(pixel_color));
+						state.select_palette(index);
 					}
 
 					Ok(())
@@ -193,7 +242,7 @@ pub fn create_pixels_container(top: HtmlDivElement) -> Result<ItemContainer> {
 		}
 	}
 
-	Ok(cont)
+	Ok(())
 }
 
 
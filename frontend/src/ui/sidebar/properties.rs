@@ -0,0 +1,172 @@
+use std::cell::Cell;
+
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{Event, HtmlDivElement, HtmlInputElement, HtmlLabelElement, HtmlSpanElement};
+
+use crate::{
+	Result, window,
+	editor::state::canvas::CanvasEvent,
+	ids::ObjectId,
+	objects::{Property, PropertyValue},
+	statics::{get_editor_state, get_editor_state_mut}
+};
+
+use super::ItemContainer;
+
+
+/// How often the panel checks for a different selection.
+const REFRESH_EVERY: i32 = 250;
+
+
+struct PropertiesPanel {
+	container: ItemContainer,
+
+	/// Selected Object and history revision it was rendered at.
+	rendered: Cell<Option<(ObjectId, usize)>>
+}
+
+impl PropertiesPanel {
+	/// Shows the panel while an Object is selected. Re-renders it once the Object was edited.
+	fn refresh(&self) -> Result<()> {
+		let selected = get_editor_state().get_canvas_state().and_then(|canvas| {
+			match canvas.event.as_ref()? {
+				CanvasEvent::SelectedObject(id) |
+				CanvasEvent::ResizingObject(id, _) |
+				CanvasEvent::MovingNode(id, _, _) => Some((*id, canvas.history.revision())),
+				_ => None
+			}
+		});
+
+		if selected == self.rendered.get() {
+			return Ok(());
+		}
+
+		self.rendered.set(selected);
+
+		match selected {
+			Some((id, _)) => {
+				self.render_object(id)?;
+				self.container.render()?;
+			}
+
+			None => self.container.remove()
+		}
+
+		Ok(())
+	}
+
+	fn render_object(&self, id: ObjectId) -> Result<()> {
+		let inner = &self.container.container_inner;
+
+		inner.set_inner_html("");
+
+		let Some(canvas) = get_editor_state().get_canvas_state() else { return Ok(()) };
+		let Some(object) = canvas.pixels.get_object_by_id(id) else { return Ok(()) };
+
+		{ // Info
+			let info = crate::create_element::<HtmlDivElement>("div");
+			info.set_class_name("info");
+			inner.append_with_node_1(&info)?;
+
+			let (x, y) = object.get_cell_pos();
+			let dimensions = object.get_dimensions();
+
+			for text in [
+				format!("Type: {}", object.name()),
+				format!("Position: ({x}, {y})"),
+				format!("Dimensions: {} x {}", dimensions.0, dimensions.1)
+			] {
+				let span = crate::create_element::<HtmlSpanElement>("span");
+				span.set_inner_text(&text);
+				info.append_with_node_1(&span)?;
+			}
+		}
+
+		for property in object.properties() {
+			render_property(inner, id, property)?;
+		}
+
+		Ok(())
+	}
+}
+
+
+fn render_property(parent: &HtmlDivElement, id: ObjectId, property: Property) -> Result<()> {
+	let label = crate::create_element::<HtmlLabelElement>("label");
+	label.set_class_name("property");
+	parent.append_with_node_1(&label)?;
+
+	let title = crate::create_element::<HtmlSpanElement>("span");
+	title.set_inner_text(&property.title);
+	label.append_with_node_1(&title)?;
+
+	let input = crate::create_element::<HtmlInputElement>("input");
+	label.append_with_node_1(&input)?;
+
+	match &property.value {
+		PropertyValue::Bool(value) => {
+			input.set_type("checkbox");
+			input.set_checked(*value);
+		}
+
+		PropertyValue::Number { value, min, max } => {
+			input.set_type("number");
+			input.set_min(&min.to_string());
+			input.set_max(&max.to_string());
+			input.set_value(&value.to_string());
+		}
+
+		PropertyValue::Text(value) => {
+			input.set_type("text");
+			input.set_value(value);
+		}
+	}
+
+	{ // On change
+		let input_copied = input.clone();
+
+		let closure = Closure::wrap(Box::new(move |_: Event| {
+			let value = match &property.value {
+				PropertyValue::Bool(_) => PropertyValue::Bool(input_copied.checked()),
+
+				&PropertyValue::Number { value, min, max } => PropertyValue::Number {
+					value: input_copied.value().trim().parse::<i64>().unwrap_or(value).clamp(min, max),
+					min,
+					max
+				},
+
+				PropertyValue::Text(_) => PropertyValue::Text(input_copied.value())
+			};
+
+			if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+				canvas.set_object_property(id, &property.key, value);
+			}
+		}) as Box<dyn FnMut(_)>);
+		input.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	Ok(())
+}
+
+
+pub fn create_properties_container(top: HtmlDivElement) -> Result<ItemContainer> {
+	let cont = ItemContainer::new("Properties", "properties", top)?;
+
+	let panel = PropertiesPanel {
+		container: cont.clone(),
+		rendered: Cell::new(None)
+	};
+
+	{ // Refresh
+		let closure = Closure::wrap(Box::new(move || {
+			if let Err(e) = panel.refresh() {
+				log!("Properties Error: {:?}", e);
+			}
+		}) as Box<dyn FnMut()>);
+		window().set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), REFRESH_EVERY)?;
+		closure.forget();
+	}
+
+	Ok(cont)
+}
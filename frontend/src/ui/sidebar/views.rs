@@ -0,0 +1,208 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{HtmlDivElement, HtmlInputElement, HtmlLiElement, HtmlSpanElement, HtmlUListElement, MouseEvent};
+
+use circuit_sim_common::config::ViewBookmarkJson;
+
+use crate::{
+	Editor, NotificationType, Result, window,
+	editor::Command,
+	statics::{get_editor_state, get_editor_state_mut}
+};
+
+use super::ItemContainer;
+
+
+/// How often the list checks for bookmarks of a different Canvas.
+const REFRESH_EVERY: i32 = 250;
+
+
+struct ViewsPanel {
+	editor: Editor,
+
+	list: HtmlUListElement,
+	name: HtmlInputElement,
+
+	rendered: RefCell<Vec<ViewBookmarkJson>>
+}
+
+impl ViewsPanel {
+	/// Re-renders the list if the bookmarks changed.
+	fn refresh(self: &Rc<Self>) -> Result<()> {
+		let bookmarks = match get_editor_state().get_canvas_state() {
+			Some(canvas) => canvas.bookmarks.clone(),
+			None => Vec::new()
+		};
+
+		if *self.rendered.borrow() == bookmarks {
+			return Ok(());
+		}
+
+		self.list.set_inner_html("");
+
+		for (index, bookmark) in bookmarks.iter().enumerate() {
+			self.render_bookmark(index, bookmark)?;
+		}
+
+		self.rendered.replace(bookmarks);
+
+		Ok(())
+	}
+
+	fn render_bookmark(self: &Rc<Self>, index: usize, bookmark: &ViewBookmarkJson) -> Result<()> {
+		let list_item = crate::create_element::<HtmlLiElement>("li");
+		list_item.set_class_name("item");
+		self.list.append_with_node_1(&list_item)?;
+
+		let title = crate::create_element::<HtmlSpanElement>("span");
+		title.set_class_name("title");
+		title.set_inner_text(&bookmark.name);
+		list_item.append_with_node_1(&title)?;
+
+		let remove = crate::create_element::<HtmlSpanElement>("span");
+		remove.set_class_name("remove");
+		remove.set_inner_text("x");
+		remove.set_title("Remove View");
+		list_item.append_with_node_1(&remove)?;
+
+		{ // On click. Goes to the view.
+			let panel = self.clone();
+			let bookmark = bookmark.clone();
+
+			let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+				if let Ok(mut editor) = panel.editor.write() {
+					editor.view_opts.set_view(bookmark.center, bookmark.pixel_size);
+				}
+			}) as Box<dyn FnMut(_)>);
+			list_item.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		{ // On remove click
+			let panel = self.clone();
+
+			let closure = Closure::wrap(Box::new(move |e: MouseEvent| {
+				e.stop_propagation();
+
+				if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+					if index < canvas.bookmarks.len() {
+						canvas.bookmarks.remove(index);
+					}
+				}
+
+				if let Err(e) = panel.refresh() {
+					log!("Views Error: {:?}", e);
+				}
+			}) as Box<dyn FnMut(_)>);
+			remove.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+			closure.forget();
+		}
+
+		Ok(())
+	}
+
+	/// Bookmarks the current view under the typed name.
+	fn save_view(self: &Rc<Self>) -> Result<()> {
+		let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() else { return Ok(()) };
+
+		let view = self.editor.read()?.view_opts.clone();
+
+		let name = match self.name.value().trim() {
+			"" => format!("View {}", canvas.bookmarks.len() + 1),
+			name => name.to_string()
+		};
+
+		canvas.bookmarks.push(ViewBookmarkJson {
+			name,
+			center: view.view_center(),
+			pixel_size: view.pixel_size
+		});
+
+		self.name.set_value("");
+
+		self.refresh()
+	}
+}
+
+
+pub fn create_views_container(top: HtmlDivElement, editor: Editor) -> Result<ItemContainer> {
+	let cont = ItemContainer::new("Views", "views", top)?;
+
+	let controls = crate::create_element::<HtmlDivElement>("div");
+	controls.set_class_name("controls");
+	cont.container_inner.append_with_node_1(&controls)?;
+
+	for command in [Command::ZoomToFit, Command::ZoomToSelection] {
+		let button = crate::create_element::<HtmlDivElement>("div");
+		button.set_class_name("button");
+		button.set_inner_text(&command.display_name());
+		controls.append_with_node_1(&button)?;
+
+		let editor = editor.clone();
+
+		let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+			let run = || -> Result<()> {
+				editor.write()?.run_command(command)
+			};
+
+			if let Err(e) = run() {
+				log!("Views Error: {:?}", e);
+				crate::statics::create_notification("Views", NotificationType::Error(e), 1000 * 10).expect("notification");
+			}
+		}) as Box<dyn FnMut(_)>);
+		button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	let save = crate::create_element::<HtmlDivElement>("div");
+	save.set_class_name("save");
+	cont.container_inner.append_with_node_1(&save)?;
+
+	let name = crate::create_element::<HtmlInputElement>("input");
+	name.set_type("text");
+	name.set_placeholder("View Name");
+	save.append_with_node_1(&name)?;
+
+	let button_save = crate::create_element::<HtmlDivElement>("div");
+	button_save.set_class_name("button");
+	button_save.set_inner_text("Save View");
+	save.append_with_node_1(&button_save)?;
+
+	let list = crate::create_element::<HtmlUListElement>("ul");
+	list.set_class_name("item-list");
+	cont.container_inner.append_with_node_1(&list)?;
+
+	let panel = Rc::new(ViewsPanel {
+		editor,
+		list,
+		name,
+		rendered: RefCell::new(Vec::new())
+	});
+
+	{ // On Save click
+		let panel = panel.clone();
+
+		let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+			if let Err(e) = panel.save_view() {
+				log!("Views Error: {:?}", e);
+				crate::statics::create_notification("Views", NotificationType::Error(e), 1000 * 10).expect("notification");
+			}
+		}) as Box<dyn FnMut(_)>);
+		button_save.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	{ // Refresh
+		let closure = Closure::wrap(Box::new(move || {
+			if let Err(e) = panel.refresh() {
+				log!("Views Error: {:?}", e);
+			}
+		}) as Box<dyn FnMut()>);
+		window().set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), REFRESH_EVERY)?;
+		closure.forget();
+	}
+
+	Ok(cont)
+}
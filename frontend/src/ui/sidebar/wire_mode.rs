@@ -0,0 +1,116 @@
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{Event, HtmlDivElement, HtmlInputElement, HtmlLabelElement, MouseEvent};
+
+use crate::{
+	Result, window,
+	editor::drawing::WireMode,
+	statics::{get_editor_state, get_editor_state_mut}
+};
+
+use super::ItemContainer;
+
+
+/// How often the buttons check for a mode changed from a shortcut.
+const REFRESH_EVERY: i32 = 250;
+
+
+struct WireModePanel {
+	buttons: Vec<(WireMode, HtmlDivElement)>,
+	vertical_first: HtmlInputElement
+}
+
+impl WireModePanel {
+	fn refresh(&self) {
+		let Some(canvas) = get_editor_state().get_canvas_state() else { return };
+
+		for (mode, button) in &self.buttons {
+			let _ = button.class_list().toggle_with_force("selected", mode.is_same_mode(canvas.wire_mode));
+		}
+
+		let is_vertical_first = matches!(canvas.wire_mode, WireMode::LShape { vertical_first: true });
+
+		self.vertical_first.set_checked(is_vertical_first);
+		self.vertical_first.set_disabled(!matches!(canvas.wire_mode, WireMode::LShape { .. }));
+	}
+}
+
+
+pub fn create_wire_mode_container(top: HtmlDivElement) -> Result<ItemContainer> {
+	let cont = ItemContainer::new("Wire Mode", "wire-mode", top)?;
+
+	let controls = crate::create_element::<HtmlDivElement>("div");
+	controls.set_class_name("controls");
+	cont.container_inner.append_with_node_1(&controls)?;
+
+	let mut buttons = Vec::new();
+
+	for mode in WireMode::list() {
+		let button = crate::create_element::<HtmlDivElement>("div");
+		button.set_class_name("button");
+		button.set_inner_text(mode.title());
+		controls.append_with_node_1(&button)?;
+
+		buttons.push((mode, button));
+	}
+
+	let label = crate::create_element::<HtmlLabelElement>("label");
+	cont.container_inner.append_with_node_1(&label)?;
+
+	let vertical_first = crate::create_element::<HtmlInputElement>("input");
+	vertical_first.set_type("checkbox");
+	label.append_with_node_1(&vertical_first)?;
+	label.append_with_str_1("Bend Vertical First")?;
+
+	let panel = Rc::new(WireModePanel { buttons, vertical_first });
+
+	for (mode, button) in &panel.buttons {
+		let mode = *mode;
+		let panel = panel.clone();
+
+		let closure = Closure::wrap(Box::new(move |_: MouseEvent| {
+			if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+				// Keep the bend direction when switching back to the L-Shape.
+				if !mode.is_same_mode(canvas.wire_mode) {
+					canvas.wire_mode = match mode {
+						WireMode::LShape { .. } => WireMode::LShape { vertical_first: panel.vertical_first.checked() },
+						mode => mode
+					};
+				}
+			}
+
+			panel.refresh();
+		}) as Box<dyn FnMut(_)>);
+		button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	{ // On Bend change
+		let panel_copied = panel.clone();
+
+		let closure = Closure::wrap(Box::new(move |_: Event| {
+			if let Some(canvas) = get_editor_state_mut().get_canvas_state_mut() {
+				if let WireMode::LShape { vertical_first } = &mut canvas.wire_mode {
+					*vertical_first = panel_copied.vertical_first.checked();
+				}
+			}
+
+			panel_copied.refresh();
+		}) as Box<dyn FnMut(_)>);
+		panel.vertical_first.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+	}
+
+	panel.refresh();
+
+	{ // Refresh
+		let closure = Closure::wrap(Box::new(move || {
+			panel.refresh();
+		}) as Box<dyn FnMut()>);
+		window().set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), REFRESH_EVERY)?;
+		closure.forget();
+	}
+
+	Ok(cont)
+}
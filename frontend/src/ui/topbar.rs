@@ -1,14 +1,20 @@
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::Closure;
-use web_sys::{HtmlDivElement, HtmlElement, HtmlSpanElement, MouseEvent};
+use web_sys::{Element, HtmlDivElement, HtmlElement, HtmlInputElement, HtmlSpanElement, HtmlTextAreaElement, MouseEvent};
 
-use crate::{Result, config::save_editor, editor::CanvasState, statics};
+use circuit_sim_common::{circuit::Circuit, config::CanvasStateJson, expr, layout, timing, verilog};
+
+use crate::{Error, NotificationType, Result, config::{insert_state_json, save_editor, try_from_editor_state}, editor::{CanvasState, documents::{close_document, open_document, open_saved_canvas, switch_document}, state::viewer::fork_viewed_canvas}, statics};
+
+use super::{popup::Popup, shortcuts::shortcuts_popup};
 
 
 pub struct Topbar {
 	container: HtmlDivElement,
 
 	row: HtmlDivElement,
+	/// One tab for every open document.
+	tabs: HtmlDivElement,
 	dropdown: HtmlDivElement
 }
 
@@ -17,6 +23,7 @@ impl Topbar {
 	pub fn new() -> Self {
 		Self {
 			row: crate::create_element::<HtmlDivElement>("div"),
+			tabs: crate::create_element::<HtmlDivElement>("div"),
 			dropdown: crate::create_element::<HtmlDivElement>("div"),
 			container: crate::create_element::<HtmlDivElement>("div")
 		}
@@ -25,62 +32,328 @@ impl Topbar {
 	pub fn init(&self) {
 		self.container.set_class_name("editor-ui-top-bar");
 		self.row.set_class_name("top-bar-row");
+		self.tabs.set_class_name("top-bar-tabs");
 		self.dropdown.set_class_name("top-bar-dropdown");
 	}
 
 	pub fn render(&self, parent: &HtmlElement) -> Result<()> {
 		parent.append_with_node_1(&self.container)?;
 		self.container.append_with_node_1(&self.row)?;
+		self.container.append_with_node_1(&self.tabs)?;
 		self.container.append_with_node_1(&self.dropdown)?;
 
-		{ // New
-			let container = crate::create_element::<HtmlDivElement>("div");
-			container.set_class_name("top-bar-item-container");
+		self.add_item("New", "if-editing", || open_document(Box::new(CanvasState::new())))?;
 
-			let text_container = crate::create_element::<HtmlSpanElement>("span");
-			text_container.set_inner_text("New");
-			container.append_with_node_1(&text_container)?;
+		self.add_item("Open", "", open_canvas_popup)?;
 
-			self.row.append_with_node_1(&container)?;
+		self.add_item("Save", "if-editing", || save_editor(|_| {
+			log!("saved");
+		}))?;
 
-			// On click button
-			let closure = Closure::wrap(Box::new(move |event: MouseEvent| {
-				let _ = statics::set_editor_state(Box::new(CanvasState::new()));
+		self.add_item("Fork", "if-can-fork", fork_viewed_canvas)?;
 
-				event.prevent_default();
-			}) as Box<dyn FnMut(_)>);
-			container.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
-			closure.forget();
-		}
+		self.add_item("Synthesize", "if-editing", synthesize_popup)?;
 
-		{ // Save
-			let container = crate::create_element::<HtmlDivElement>("div");
-			container.set_class_name("top-bar-item-container");
+		self.add_item("Import Verilog", "if-editing", import_verilog_popup)?;
 
-			let text_container = crate::create_element::<HtmlSpanElement>("span");
-			text_container.set_inner_text("Save");
-			container.append_with_node_1(&text_container)?;
+		self.add_item("Export Verilog", "", export_verilog)?;
 
-			self.row.append_with_node_1(&container)?;
+		self.add_item("Timing", "if-editing", timing_popup)?;
 
-			// On click button
-			let closure = Closure::wrap(Box::new(move |event: MouseEvent| {
-				let res = save_editor(|_| {
-					log!("saved");
-				});
+		self.add_item("Shortcuts", "", shortcuts_popup)?;
 
-				if let Err(e) = res {
-					log!("Save Error: {:?}", e);
-				}
+		self.listen_to_tabs()?;
+		self.render_tabs()?;
+
+		Ok(())
+	}
 
-				event.prevent_default();
-			}) as Box<dyn FnMut(_)>);
-			container.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
-			closure.forget();
+	/// Draws a tab for every open document. Called again once one is opened, closed, switched to or renamed.
+	pub fn render_tabs(&self) -> Result<()> {
+		self.tabs.set_inner_html("");
+
+		let documents = statics::get_documents();
+
+		for (index, title) in documents.titles().into_iter().enumerate() {
+			let tab = crate::create_element::<HtmlDivElement>("div");
+			tab.set_class_name(if index == documents.active() { "top-bar-tab active" } else { "top-bar-tab" });
+			tab.set_attribute("data-index", &index.to_string())?;
+			self.tabs.append_with_node_1(&tab)?;
+
+			let span_title = crate::create_element::<HtmlSpanElement>("span");
+			span_title.set_inner_text(&title);
+			tab.append_with_node_1(&span_title)?;
+
+			let span_close = crate::create_element::<HtmlSpanElement>("span");
+			span_close.set_class_name("close");
+			span_close.set_inner_text("x");
+			tab.append_with_node_1(&span_close)?;
 		}
 
 		Ok(())
 	}
+
+	/// Tabs are drawn again often so their clicks are handled by the container.
+	fn listen_to_tabs(&self) -> Result<()> {
+		let closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+			let Some(target) = event.target().and_then(|t| t.dyn_into::<Element>().ok()) else { return };
+
+			let Ok(Some(tab)) = target.closest(".top-bar-tab") else { return };
+			let Some(index) = tab.get_attribute("data-index").and_then(|v| v.parse().ok()) else { return };
+
+			let result = if matches!(target.closest(".close"), Ok(Some(_))) {
+				close_document(index, false)
+			} else {
+				switch_document(index)
+			};
+
+			if let Err(e) = result {
+				log!("Tab Error: {:?}", e);
+			}
+
+			event.prevent_default();
+		}) as Box<dyn FnMut(_)>);
+		self.tabs.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+
+		Ok(())
+	}
+
+	/// `class` is added to the item. Used to only show it while editing or viewing.
+	fn add_item<F>(&self, title: &str, class: &str, mut on_click: F) -> Result<()> where F: FnMut() -> Result<()> + 'static {
+		let container = crate::create_element::<HtmlDivElement>("div");
+		container.set_class_name(format!("top-bar-item-container {class}").trim_end());
+
+		let text_container = crate::create_element::<HtmlSpanElement>("span");
+		text_container.set_inner_text(title);
+		container.append_with_node_1(&text_container)?;
+
+		self.row.append_with_node_1(&container)?;
+
+		let title = title.to_string();
+
+		// On click button
+		let closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+			if let Err(e) = on_click() {
+				log!("{} Error: {:?}", title, e);
+			}
+
+			event.prevent_default();
+		}) as Box<dyn FnMut(_)>);
+		container.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+		closure.forget();
+
+		Ok(())
+	}
+}
+
+
+/// Asks for the Id of a saved Canvas and opens it in a new tab.
+pub fn open_canvas_popup() -> Result<()> {
+	let popup = Popup::new("Open Canvas")?;
+
+	let input = {
+		let row = popup.add_row()?;
+
+		let info = crate::create_element::<HtmlSpanElement>("span");
+		info.set_inner_text("Id of the saved Canvas. It's opened next to the current one.");
+		row.append_with_node_1(&info)?;
+
+		let row = popup.add_row()?;
+
+		let input = crate::create_element::<HtmlInputElement>("input");
+		input.set_type("text");
+		input.set_placeholder("Canvas Id");
+		row.append_with_node_1(&input)?;
+
+		input
+	};
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Open", move |_| {
+			let canvas_id = input.value().trim().to_string();
+
+			if canvas_id.is_empty() {
+				return;
+			}
+
+			popup_copied.close();
+
+			if let Err(e) = open_saved_canvas(canvas_id) {
+				let _ = statics::create_notification("Open Canvas", NotificationType::Error(e), 0);
+			}
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Cancel", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
+}
+
+
+pub fn export_verilog() -> Result<()> {
+	let state = statics::get_editor_state();
+
+	let canvas = state.get_canvas_state().ok_or_else(|| Error::String(String::from("Not editing a Canvas")))?;
+
+	let name = state.get_state_info().title.unwrap_or_else(|| String::from("top"));
+
+	let value = verilog::export_module(&name, &try_from_editor_state(canvas)?);
+
+	crate::download_file(&format!("{}.v", verilog::identifier(&name)), &value)
+}
+
+
+pub fn timing_popup() -> Result<()> {
+	let canvas = statics::get_editor_state_mut()
+		.get_canvas_state_mut()
+		.ok_or_else(|| Error::String(String::from("Not editing a Canvas")))?;
+
+	let report = match timing::critical_path(&try_from_editor_state(canvas)?) {
+		Ok(Some(v)) => v,
+		Ok(None) => return statics::create_notification("Timing", NotificationType::Info(String::from("No input is connected to an LED.")), 1000 * 5),
+		Err(e) => return statics::create_notification("Timing", NotificationType::ErrorStr(e), 0)
+	};
+
+	canvas.critical_path.clone_from(&report.wires);
+	canvas.critical_path_revision = canvas.history.revision();
+
+	statics::request_render();
+
+	let popup = Popup::new("Critical Path")?;
+
+	{ // Summary
+		let row = popup.add_row()?;
+
+		let frequency = report.max_frequency()
+			.map_or_else(|| String::from("Unlimited"), |v| format!("{v:.1} MHz"));
+
+		let summary = crate::create_element::<HtmlSpanElement>("span");
+		summary.set_inner_text(&format!("Total Delay: {:.1} ns. Max Clock Frequency: {frequency}", report.delay));
+		row.append_with_node_1(&summary)?;
+	}
+
+	for step in &report.steps {
+		let row = popup.add_row()?;
+
+		let object = &canvas.pixels.objects()[step.object];
+		let (x, y) = object.get_cell_pos();
+
+		let span = crate::create_element::<HtmlSpanElement>("span");
+		span.set_inner_text(&format!("{:.1} ns - {} at ({x}, {y})", step.arrival, object.display_name()));
+		row.append_with_node_1(&span)?;
+	}
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Clear Highlight", move |_| {
+			if let Some(canvas) = statics::get_editor_state_mut().get_canvas_state_mut() {
+				canvas.critical_path.clear();
+			}
+
+			statics::request_render();
+
+			popup_copied.close();
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Close", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
+}
+
+
+pub fn synthesize_popup() -> Result<()> {
+	generate_popup(
+		"Synthesize Circuit",
+		"One `output = expression` per line using ! & ^ | and parentheses, or a truth table with an `a b | y` header.",
+		"sum = a ^ b ^ cin\ncarry = (a & b) | (cin & (a ^ b))",
+		|value| {
+			let assignments = expr::parse_assignments(value).map_err(|e| e.to_string())?;
+			let circuit = Circuit::from_assignments(&assignments)?;
+
+			layout::layout_circuit(&circuit)
+		}
+	)
+}
+
+pub fn import_verilog_popup() -> Result<()> {
+	generate_popup(
+		"Import Verilog",
+		"A structural Verilog netlist using input, output, wire, assign, gate primitives and module instances.",
+		"module half_adder (a, b, sum, carry);\n\tinput a, b;\n\toutput sum, carry;\n\n\txor (sum, a, b);\n\tand (carry, a, b);\nendmodule",
+		verilog::import_canvas
+	)
+}
+
+
+/// Popup with a textarea. Generate inserts the canvas created from its value into the current Canvas.
+fn generate_popup<F>(title: &str, info_text: &str, placeholder: &str, generate: F) -> Result<()>
+where F: Fn(&str) -> std::result::Result<CanvasStateJson, String> + 'static {
+	let popup = Popup::new(title)?;
+
+	let input = {
+		let row = popup.add_row()?;
+
+		let info = crate::create_element::<HtmlSpanElement>("span");
+		info.set_inner_text(info_text);
+		row.append_with_node_1(&info)?;
+
+		let row = popup.add_row()?;
+
+		let input = crate::create_element::<HtmlTextAreaElement>("textarea");
+		input.set_placeholder(placeholder);
+		row.append_with_node_1(&input)?;
+
+		input
+	};
+
+	{ // Buttons
+		let row = popup.add_row()?;
+
+		let popup_copied = popup.clone();
+		let title = title.to_string();
+
+		popup.add_button(&row, "Generate", move |_| {
+			let process = || -> std::result::Result<(), String> {
+				let json = generate(&input.value())?;
+
+				let canvas = statics::get_editor_state_mut()
+					.get_canvas_state_mut()
+					.ok_or_else(|| String::from("Not editing a Canvas"))?;
+
+				insert_state_json(canvas, json).map_err(|e| format!("{e:?}"))
+			};
+
+			let (notification, display_time) = match process() {
+				Ok(()) => {
+					popup_copied.close();
+					(NotificationType::Success(String::from("Circuit Generated")), 1000 * 5)
+				}
+
+				Err(e) => (NotificationType::ErrorStr(e), 0)
+			};
+
+			let _ = statics::create_notification(&title, notification, display_time);
+		})?;
+
+		let popup_copied = popup.clone();
+
+		popup.add_button(&row, "Cancel", move |_| popup_copied.close())?;
+	}
+
+	Ok(())
 }
 
 
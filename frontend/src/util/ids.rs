@@ -6,6 +6,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 // No Zeros. Zero is a default.
 pub static LAST_OBJECT_ID: AtomicUsize = AtomicUsize::new(1);
 pub static LAST_TEXT_ID: AtomicUsize = AtomicUsize::new(1);
+pub static LAST_DOCUMENT_ID: AtomicUsize = AtomicUsize::new(1);
 
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -14,6 +15,10 @@ pub struct TextId(pub usize);
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ObjectId(pub usize);
 
+/// Tells open Canvases apart. Isn't saved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DocumentId(pub usize);
+
 
 macro_rules! gen_impl {
 	($type:ident, $cache:ident) => {
@@ -26,6 +31,13 @@ macro_rules! gen_impl {
 				$cache.store(1, Ordering::Relaxed);
 			}
 
+			/// Continues generating after `last`. Used once saved Ids are loaded.
+			///
+			/// Never goes back. Ids handed out before could still be in an open Canvas or its history.
+			pub fn seed(last: usize) {
+				$cache.fetch_max(last + 1, Ordering::SeqCst);
+			}
+
 			pub fn empty() -> Self {
 				Self(0)
 			}
@@ -47,4 +59,5 @@ macro_rules! gen_impl {
 
 
 gen_impl!(ObjectId, LAST_OBJECT_ID);
-gen_impl!(TextId, LAST_TEXT_ID);
\ No newline at end of file
+gen_impl!(TextId, LAST_TEXT_ID);
+gen_impl!(DocumentId, LAST_DOCUMENT_ID);
\ No newline at end of file
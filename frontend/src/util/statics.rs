@@ -1,9 +1,12 @@
 pub use user::*;
 pub use running::*;
+pub use rendering::*;
 pub use notifications::*;
 pub use ticking::*;
 pub use state::*;
 pub use ui::*;
+pub use keymap::*;
+pub use documents::*;
 
 pub mod state {
 	use crate::Result;
@@ -21,9 +24,11 @@ pub mod state {
 		unsafe { &mut **EDITOR_STATE.as_mut().unwrap() }
 	}
 
+	/// Replaces the document of the shown tab.
 	pub fn set_editor_state(state: Box<dyn EditorState>) -> Result<()> {
 		replace_editor_state(state)?;
-		Ok(())
+
+		get_ui().read()?.topbar.render_tabs()
 	}
 
 	pub fn replace_editor_state(state: Box<dyn EditorState>) -> Result<Box<dyn EditorState>> {
@@ -42,6 +47,8 @@ pub mod state {
 			get_editor_state_mut().init(&mut *editor)?;
 		}
 
+		super::request_render();
+
 		Ok(old_state)
 	}
 }
@@ -72,6 +79,38 @@ pub mod user {
 }
 
 
+// KEYBOARD SHORTCUTS
+pub mod keymap {
+	use crate::editor::keymap::Keymap;
+
+	static mut KEYMAP: Option<Keymap> = None;
+
+	/// Loaded from Local Storage on first use and again once a different user logs in.
+	pub fn get_keymap<'a>() -> &'a mut Keymap {
+		let keymap = unsafe { &mut *std::ptr::addr_of_mut!(KEYMAP) };
+
+		if !keymap.as_ref().is_some_and(Keymap::is_current_user) {
+			*keymap = None;
+		}
+
+		keymap.get_or_insert_with(Keymap::load)
+	}
+}
+
+
+// OPEN DOCUMENTS
+pub mod documents {
+	use crate::editor::documents::Documents;
+
+	static mut DOCUMENTS: Option<Documents> = None;
+
+	/// Every open document. The shown one is still in `EDITOR_STATE`.
+	pub fn get_documents<'a>() -> &'a mut Documents {
+		unsafe { (*std::ptr::addr_of_mut!(DOCUMENTS)).get_or_insert_with(Documents::new) }
+	}
+}
+
+
 // NOTIFICATION MANAGER
 pub mod notifications {
 	use crate::{NotificationType, Result};
@@ -123,6 +162,22 @@ pub mod running {
 }
 
 
+// Redrawing the Canvas
+pub mod rendering {
+	static mut IS_RENDER_REQUESTED: bool = true;
+
+	/// Draws the Canvas again on the next animation frame. Needed for changes which aren't cells, the view or the history.
+	pub fn request_render() {
+		unsafe { IS_RENDER_REQUESTED = true; }
+	}
+
+	/// Was a render requested since it was last called.
+	pub fn take_render_request() -> bool {
+		unsafe { std::mem::replace(&mut *std::ptr::addr_of_mut!(IS_RENDER_REQUESTED), false) }
+	}
+}
+
+
 // How the editor ticking works.
 pub mod ticking {
 	use std::mem;
@@ -176,6 +231,13 @@ pub mod ticking {
 		}
 	}
 
+	/// Swaps the queued ticks. Used to keep them with the document they were queued in.
+	pub fn replace_ticking(queries: Vec<ObjectData>) -> Vec<ObjectData> {
+		unsafe {
+			mem::replace(&mut (*std::ptr::addr_of_mut!(EDITOR_TICKING)).queries, queries)
+		}
+	}
+
 	pub fn tick() -> Result<()> {
 		unsafe {
 			EDITOR_TICKING.tick_all()